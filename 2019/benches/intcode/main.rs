@@ -0,0 +1,78 @@
+use advent_of_code_2019::{Engine, IntcodeComputer, Program, RunState};
+use std::time::{Duration, Instant};
+
+mod baseline;
+
+const MIN_ITERATIONS: u32 = 20;
+const MIN_DURATION: Duration = Duration::from_millis(500);
+
+fn parse(source: &str) -> Program {
+    source.parse().unwrap()
+}
+
+/// Repeats `run` for at least `MIN_ITERATIONS` and `MIN_DURATION`, returning
+/// the average time taken per run.
+fn bench(mut run: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    let mut iterations = 0;
+    while iterations < MIN_ITERATIONS || start.elapsed() < MIN_DURATION {
+        run();
+        iterations += 1;
+    }
+    start.elapsed() / iterations
+}
+
+/// Runs `program` to completion with `input` using `engine`.
+fn run(program: &[i64], input: i64, engine: Engine) -> Vec<i64> {
+    let mut computer = IntcodeComputer::new(program);
+    computer.set_engine(engine);
+    computer.input(input);
+    assert_eq!(computer.run().unwrap(), RunState::Halted);
+    computer.output().to_vec()
+}
+
+/// Runs `program` with the interpreter from before engines were added.
+fn run_baseline(program: &[i64], input: i64) -> Vec<i64> {
+    let mut computer = baseline::IntcodeComputer::new(program);
+    computer.input(input);
+    computer.run().unwrap();
+    computer.output().to_vec()
+}
+
+fn main() {
+    let day05 = parse(include_str!("../../input/day05.txt"));
+    let day09 = parse(include_str!("../../input/day09.txt"));
+    let cases = [
+        ("day05 part 2", &day05, 5),
+        ("day09 part 1", &day09, 1),
+        ("day09 part 2", &day09, 2),
+    ];
+
+    println!(
+        "{:<14} {:>14} {:>14} {:>14} {:>9}",
+        "program", "baseline", "interpreter", "cached", "speedup"
+    );
+    for (name, program, input) in cases.iter() {
+        let output = run_baseline(program, *input);
+        assert_eq!(run(program, *input, Engine::Interpreter), output);
+        assert_eq!(run(program, *input, Engine::Cached), output);
+
+        let baseline = bench(|| {
+            run_baseline(program, *input);
+        });
+        let interpreter = bench(|| {
+            run(program, *input, Engine::Interpreter);
+        });
+        let cached = bench(|| {
+            run(program, *input, Engine::Cached);
+        });
+        println!(
+            "{:<14} {:>14?} {:>14?} {:>14?} {:>8.2}x",
+            name,
+            baseline,
+            interpreter,
+            cached,
+            interpreter.as_secs_f64() / cached.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,484 @@
+use crate::intcode::{CustomOpcode, Opcode, Operand};
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("{line}:{column}: {kind}")]
+pub struct AssembleError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: AssembleErrorKind,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AssembleErrorKind {
+    #[error("unknown mnemonic '{0}'")]
+    UnknownMnemonic(String),
+    #[error("'{mnemonic}' expects {expected} operand(s), found {found}")]
+    OperandCount {
+        mnemonic: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    #[error("unknown label '{0}'")]
+    UnknownLabel(String),
+    #[error("duplicate label '{0}'")]
+    DuplicateLabel(String),
+    #[error("destination operand cannot be immediate")]
+    ImmediateDestination,
+    #[error("invalid number '{0}'")]
+    InvalidNumber(String),
+    #[error("expected {0}")]
+    Expected(&'static str),
+}
+
+#[derive(Debug)]
+enum Expr {
+    Number(i64),
+    Label { name: String, offset: i64 },
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Mode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+#[derive(Debug)]
+struct Arg {
+    mode: Mode,
+    expr: Expr,
+    column: usize,
+}
+
+#[derive(Debug)]
+enum Statement {
+    Instruction(Opcode, Vec<Arg>),
+    Data(Vec<Arg>),
+}
+
+impl Statement {
+    fn size(&self) -> usize {
+        match self {
+            Statement::Instruction(opcode, _) => 1 + opcode.arity(),
+            Statement::Data(values) => values.len(),
+        }
+    }
+}
+
+/// A cursor over a single line of source, tracking the current column.
+struct Line<'a> {
+    number: usize,
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Line<'a> {
+    fn new(number: usize, src: &'a str) -> Self {
+        // Strip comments
+        let src = src.find(';').map_or(src, |idx| &src[..idx]);
+        Self {
+            number,
+            src,
+            pos: 0,
+        }
+    }
+
+    fn column(&self) -> usize {
+        self.src[..self.pos].chars().count() + 1
+    }
+
+    fn error(&self, column: usize, kind: AssembleErrorKind) -> AssembleError {
+        AssembleError {
+            line: self.number,
+            column,
+            kind,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn at_end(&mut self) -> bool {
+        self.skip_whitespace();
+        self.rest().is_empty()
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.rest().chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char, what: &'static str) -> Result<(), AssembleError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(self.column(), AssembleErrorKind::Expected(what)))
+        }
+    }
+
+    fn ident(&mut self) -> Option<&'a str> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .char_indices()
+            .find(|&(idx, c)| {
+                !(c == '_'
+                    || c == '.'
+                    || c.is_ascii_alphabetic()
+                    || (idx > 0 && c.is_ascii_digit()))
+            })
+            .map_or(rest.len(), |(idx, _)| idx);
+        if len == 0 {
+            None
+        } else {
+            self.pos += len;
+            Some(&rest[..len])
+        }
+    }
+
+    fn number(&mut self) -> Result<i64, AssembleError> {
+        self.skip_whitespace();
+        let column = self.column();
+        let rest = self.rest();
+        let len = rest
+            .char_indices()
+            .find(|&(idx, c)| !(c.is_ascii_digit() || (idx == 0 && c == '-')))
+            .map_or(rest.len(), |(idx, _)| idx);
+        if len == 0 {
+            return Err(self.error(column, AssembleErrorKind::Expected("number")));
+        }
+        self.pos += len;
+        rest[..len].parse().map_err(|_| {
+            self.error(
+                column,
+                AssembleErrorKind::InvalidNumber(rest[..len].to_owned()),
+            )
+        })
+    }
+
+    fn expr(&mut self) -> Result<Expr, AssembleError> {
+        match self.peek() {
+            Some(c) if c.is_ascii_digit() || c == '-' => Ok(Expr::Number(self.number()?)),
+            _ => {
+                let name = self.ident().ok_or_else(|| {
+                    self.error(
+                        self.column(),
+                        AssembleErrorKind::Expected("number or label"),
+                    )
+                })?;
+                let offset = if self.eat('+') || self.peek() == Some('-') {
+                    self.number()?
+                } else {
+                    0
+                };
+                Ok(Expr::Label {
+                    name: name.to_owned(),
+                    offset,
+                })
+            }
+        }
+    }
+
+    fn arg(&mut self) -> Result<Arg, AssembleError> {
+        self.skip_whitespace();
+        let column = self.column();
+        let (mode, expr) = if self.eat('#') {
+            (Mode::Immediate, self.expr()?)
+        } else if self.eat('[') {
+            let expr = self.expr()?;
+            self.expect(']', "']'")?;
+            (Mode::Position, expr)
+        } else if self.rest().starts_with("rb") {
+            self.pos += 2;
+            let expr = match self.peek() {
+                Some('+') => {
+                    self.pos += 1;
+                    self.expr()?
+                }
+                Some('-') => Expr::Number(self.number()?),
+                _ => Expr::Number(0),
+            };
+            (Mode::Relative, expr)
+        } else {
+            return Err(self.error(column, AssembleErrorKind::Expected("operand")));
+        };
+        Ok(Arg { mode, expr, column })
+    }
+
+    fn value(&mut self) -> Result<Arg, AssembleError> {
+        self.skip_whitespace();
+        let column = self.column();
+        Ok(Arg {
+            mode: Mode::Immediate,
+            expr: self.expr()?,
+            column,
+        })
+    }
+
+    fn list(
+        &mut self,
+        item: fn(&mut Self) -> Result<Arg, AssembleError>,
+    ) -> Result<Vec<Arg>, AssembleError> {
+        let mut args = Vec::new();
+        if self.at_end() {
+            return Ok(args);
+        }
+        loop {
+            args.push(item(self)?);
+            if self.at_end() {
+                return Ok(args);
+            }
+            self.expect(',', "','")?;
+        }
+    }
+}
+
+/// Assembles Intcode source into a program suitable for `IntcodeComputer::new`.
+///
+/// Each line holds an optional `label:`, followed by either an instruction
+/// using the disassembler's mnemonics or a `data` directive. Operands are
+/// written `[addr]` for position mode, `#value` for immediate mode and
+/// `rb+offset` for relative mode, where addresses and values may be numbers,
+/// labels or `label+offset`. Data values are plain numbers or labels.
+/// Everything after a `;` is a comment.
+pub fn assemble(source: &str) -> Result<Vec<i64>, AssembleError> {
+    assemble_with(source, &[])
+}
+
+/// Like `assemble`, but also accepts the mnemonics of the `custom` opcodes.
+pub fn assemble_with(source: &str, custom: &[CustomOpcode]) -> Result<Vec<i64>, AssembleError> {
+    let mut labels = HashMap::new();
+    let mut statements = Vec::new();
+    let mut address = 0;
+
+    for (idx, src) in source.lines().enumerate() {
+        let mut line = Line::new(idx + 1, src);
+
+        line.skip_whitespace();
+        let mut column = line.column();
+        let mut word = line.ident();
+        while word.is_some() && line.eat(':') {
+            let label = word.unwrap();
+            if labels.insert(label.to_owned(), address).is_some() {
+                return Err(line.error(column, AssembleErrorKind::DuplicateLabel(label.to_owned())));
+            }
+            line.skip_whitespace();
+            column = line.column();
+            word = line.ident();
+        }
+
+        let statement = match word {
+            None if line.at_end() => continue,
+            None => return Err(line.error(line.column(), AssembleErrorKind::Expected("mnemonic"))),
+            Some("data") | Some(".data") => Statement::Data(line.list(Line::value)?),
+            Some(mnemonic) => {
+                let custom = custom
+                    .iter()
+                    .find(|opcode| opcode.mnemonic == mnemonic)
+                    .map(|&opcode| Opcode::Custom(opcode));
+                let opcode = Opcode::from_mnemonic(mnemonic).or(custom).ok_or_else(|| {
+                    line.error(
+                        column,
+                        AssembleErrorKind::UnknownMnemonic(mnemonic.to_owned()),
+                    )
+                })?;
+                let args = line.list(Line::arg)?;
+                if args.len() != opcode.arity() {
+                    return Err(line.error(
+                        column,
+                        AssembleErrorKind::OperandCount {
+                            mnemonic: opcode.mnemonic(),
+                            expected: opcode.arity(),
+                            found: args.len(),
+                        },
+                    ));
+                }
+                if let Some(arg) = opcode.destination().map(|idx| &args[idx]) {
+                    if let Mode::Immediate = arg.mode {
+                        return Err(line.error(arg.column, AssembleErrorKind::ImmediateDestination));
+                    }
+                }
+                Statement::Instruction(opcode, args)
+            }
+        };
+
+        address += statement.size();
+        statements.push((idx + 1, statement));
+    }
+
+    let resolve = |line: usize, arg: &Arg| -> Result<i64, AssembleError> {
+        match &arg.expr {
+            Expr::Number(n) => Ok(*n),
+            Expr::Label { name, offset } => {
+                let error = |kind| AssembleError {
+                    line,
+                    column: arg.column,
+                    kind,
+                };
+                let address = labels
+                    .get(name)
+                    .ok_or_else(|| error(AssembleErrorKind::UnknownLabel(name.clone())))?;
+                (*address as i64).checked_add(*offset).ok_or_else(|| {
+                    error(AssembleErrorKind::InvalidNumber(format!(
+                        "{}{:+}",
+                        name, offset
+                    )))
+                })
+            }
+        }
+    };
+
+    let mut program = Vec::with_capacity(address);
+    for (line, statement) in statements {
+        match statement {
+            Statement::Instruction(opcode, args) => {
+                let operands = args
+                    .iter()
+                    .map(|arg| {
+                        let value = resolve(line, arg)?;
+                        Ok(match arg.mode {
+                            Mode::Position => Operand::Position(value),
+                            Mode::Immediate => Operand::Immediate(value),
+                            Mode::Relative => Operand::Relative(value),
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let modes = operands
+                    .iter()
+                    .rev()
+                    .fold(0, |acc, operand| acc * 10 + operand.mode());
+                program.push(opcode.code() + modes * 100);
+                program.extend(operands.iter().map(Operand::value));
+            }
+            Statement::Data(values) => {
+                for arg in &values {
+                    program.push(resolve(line, arg)?);
+                }
+            }
+        }
+    }
+
+    Ok(program)
+}
+
+#[test]
+fn quine() {
+    let program = assemble(
+        "
+        ; Outputs a copy of itself
+        start:  arb #1
+                out rb-1
+                add [100], #1, [100]
+                eq [100], #16, [101]
+                jf [101], #start
+                hlt
+        ",
+    )
+    .unwrap();
+
+    assert_eq!(
+        program,
+        [109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99]
+    );
+}
+
+#[test]
+fn labels_and_data() {
+    let program = assemble(
+        "
+        start: in [value]
+               out [value+1]
+               jt #1, #start
+        value: data 7, -3, start
+        ",
+    )
+    .unwrap();
+
+    assert_eq!(program, [3, 7, 4, 8, 1105, 1, 0, 7, -3, 0]);
+}
+
+#[test]
+fn round_trip_disassembly() {
+    let program = [
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+    let source = crate::disassemble(&program)
+        .items()
+        .iter()
+        .map(|item| match &item.item {
+            crate::Item::Instruction(instruction) => instruction.to_string(),
+            crate::Item::Data(value) => format!("data {}", value),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    assert_eq!(assemble(&source).unwrap(), program);
+}
+
+#[test]
+fn errors() {
+    let err = |source| assemble(source).unwrap_err();
+
+    assert_eq!(
+        err("hlt\n  foo [1]"),
+        AssembleError {
+            line: 2,
+            column: 3,
+            kind: AssembleErrorKind::UnknownMnemonic("foo".into()),
+        }
+    );
+    assert_eq!(
+        err("add #1, #2, #3"),
+        AssembleError {
+            line: 1,
+            column: 13,
+            kind: AssembleErrorKind::ImmediateDestination,
+        }
+    );
+    assert_eq!(
+        err("a: hlt\na: hlt"),
+        AssembleError {
+            line: 2,
+            column: 1,
+            kind: AssembleErrorKind::DuplicateLabel("a".into()),
+        }
+    );
+    assert_eq!(
+        err("out [nowhere]").kind,
+        AssembleErrorKind::UnknownLabel("nowhere".into())
+    );
+    assert_eq!(
+        err("out [a+9223372036854775807]\na: hlt"),
+        AssembleError {
+            line: 1,
+            column: 5,
+            kind: AssembleErrorKind::InvalidNumber("a+9223372036854775807".into()),
+        }
+    );
+    assert_eq!(err("out [1").column, 7);
+    assert_eq!(
+        err("jt #1").kind,
+        AssembleErrorKind::OperandCount {
+            mnemonic: "jt",
+            expected: 2,
+            found: 1
+        }
+    );
+}
@@ -1,67 +1,167 @@
-use anyhow::Result;
+use advent_of_code_2019::{IntcodeComputer, Program};
+use anyhow::{anyhow, Result};
 use itertools::iproduct;
+use std::convert::TryFrom;
 
 const INPUT: &str = include_str!("../../input/day02.txt");
-const TARGET: usize = 19690720;
+const TARGET: i64 = 19690720;
 
-// Intcode computer
-fn run_intcode(program: &mut [usize]) -> usize {
-    let mut ip = 0;
-    loop {
-        let opcode = program[ip];
-        let rs = program[ip + 1];
-        let rt = program[ip + 2];
-        let rd = program[ip + 3];
-
-        match opcode {
-            // Add
-            1 => {
-                program[rd] = program[rs] + program[rt];
-            }
-            // Mult
-            2 => {
-                program[rd] = program[rs] * program[rt];
+/// Runs the program with the given noun and verb, returning the value left
+/// at address 0.
+fn run(program: &[i64], noun: i64, verb: i64) -> Result<i64> {
+    let mut computer = IntcodeComputer::new(program);
+    computer.poke(1, noun);
+    computer.poke(2, verb);
+    computer.run()?;
+    Ok(computer.memory().read(0))
+}
+
+/// A value in terms of the unknown noun and verb.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Value {
+    /// `Linear(c, a, b)` is `c + a * noun + b * verb`.
+    Linear(i64, i64, i64),
+    /// Depends on the noun or verb in some other way.
+    Unknown,
+}
+
+impl Value {
+    const NOUN: Value = Value::Linear(0, 1, 0);
+    const VERB: Value = Value::Linear(0, 0, 1);
+
+    fn constant(self) -> Option<i64> {
+        match self {
+            Value::Linear(c, 0, 0) => Some(c),
+            _ => None,
+        }
+    }
+
+    fn add(self, other: Value) -> Value {
+        match (self, other) {
+            (Value::Linear(c, a, b), Value::Linear(d, e, f)) => {
+                match (c.checked_add(d), a.checked_add(e), b.checked_add(f)) {
+                    (Some(c), Some(a), Some(b)) => Value::Linear(c, a, b),
+                    _ => Value::Unknown,
+                }
             }
-            // Halt
-            99 => {
-                return program[0];
+            _ => Value::Unknown,
+        }
+    }
+
+    fn mul(self, other: Value) -> Value {
+        let (factor, value) = match (self.constant(), other.constant()) {
+            (Some(factor), _) => (factor, other),
+            (_, Some(factor)) => (factor, self),
+            _ => return Value::Unknown,
+        };
+        match value {
+            Value::Linear(c, a, b) => {
+                let scale = |x: i64| x.checked_mul(factor);
+                match (scale(c), scale(a), scale(b)) {
+                    (Some(c), Some(a), Some(b)) => Value::Linear(c, a, b),
+                    _ => Value::Unknown,
+                }
             }
-            _ => unreachable!(),
+            Value::Unknown => Value::Unknown,
         }
+    }
+}
+
+/// Runs the program with the noun and verb left unknown, returning the value
+/// at address 0 once it halts.
+///
+/// Returns `None` if the program uses any opcode besides add, mul and halt,
+/// or if the noun or verb decide which instruction runs or where one writes.
+fn evaluate(program: &[i64]) -> Option<Value> {
+    let mut memory = program
+        .iter()
+        .map(|&v| Value::Linear(v, 0, 0))
+        .collect::<Vec<_>>();
+    *memory.get_mut(1)? = Value::NOUN;
+    *memory.get_mut(2)? = Value::VERB;
 
+    let mut ip = 0;
+    loop {
+        let word = memory.get(ip)?.constant()?;
+        let mode = |idx: u32| word / 10i64.pow(idx + 1) % 10;
+        let param = |idx: usize| memory.get(ip + idx).copied();
+        let operand = |idx: usize| match mode(idx as u32) {
+            0 => match param(idx)?.constant() {
+                Some(address) => {
+                    let address = usize::try_from(address).ok()?;
+                    Some(
+                        memory
+                            .get(address)
+                            .copied()
+                            .unwrap_or(Value::Linear(0, 0, 0)),
+                    )
+                }
+                // Reading through an unknown address gives an unknown value
+                None => Some(Value::Unknown),
+            },
+            1 => param(idx),
+            _ => None,
+        };
+
+        let value = match word % 100 {
+            1 => operand(1)?.add(operand(2)?),
+            2 => operand(1)?.mul(operand(2)?),
+            99 => return memory.first().copied(),
+            _ => return None,
+        };
+        if mode(3) != 0 {
+            return None;
+        }
+        let rd = usize::try_from(param(3)?.constant()?).ok()?;
+        if rd >= memory.len() {
+            memory.resize(rd + 1, Value::Linear(0, 0, 0));
+        }
+        memory[rd] = value;
         ip += 4;
     }
 }
 
-fn part2(program: &[usize], target: usize) -> (usize, usize) {
-    let mut p = program.to_owned();
-    for (noun, verb) in iproduct!(0..=99, 0..=99) {
-        p.copy_from_slice(program);
-        p[1] = noun;
-        p[2] = verb;
-
-        if run_intcode(&mut p) == target {
-            return (noun, verb);
+/// Finds the noun and verb, each between 0 and 99, for which `output` is
+/// `target`, preferring the smallest noun.
+///
+/// Returns `None` if `output` isn't linear or solving it would overflow.
+fn solve_linear(output: Value, target: i64) -> Option<Option<(i64, i64)>> {
+    let (c, a, b) = match output {
+        Value::Linear(c, a, b) => (c, a, b),
+        Value::Unknown => return None,
+    };
+    for noun in 0..=99 {
+        let rest = target.checked_sub(c)?.checked_sub(a.checked_mul(noun)?)?;
+        let verb = match b {
+            0 if rest == 0 => Some(0),
+            0 => None,
+            _ if rest.checked_rem(b)? == 0 => Some(rest / b).filter(|v| (0..=99).contains(v)),
+            _ => None,
+        };
+        if let Some(verb) = verb {
+            return Some(Some((noun, verb)));
         }
     }
-    unreachable!();
+    Some(None)
+}
+
+fn part2(program: &[i64], target: i64) -> Option<(i64, i64)> {
+    match evaluate(program).and_then(|output| solve_linear(output, target)) {
+        Some(solution) => solution,
+        // Fall back to trying every noun and verb
+        None => iproduct!(0..=99, 0..=99)
+            .find(|&(noun, verb)| run(program, noun, verb).ok() == Some(target)),
+    }
 }
 
 fn main() -> Result<()> {
-    let program = INPUT
-        .trim()
-        .split(',')
-        .map(str::parse::<usize>)
-        .collect::<Result<Vec<_>, _>>()?;
+    let program = INPUT.parse::<Program>()?;
 
     // Reset "1202 program alarm" state
-    let mut part1 = program.clone();
-    part1[1] = 12;
-    part1[2] = 2;
+    println!("Part 1: {}", run(&program, 12, 2)?);
 
-    println!("Part 1: {}", run_intcode(&mut part1));
-
-    let (noun, verb) = part2(&program, TARGET);
+    let (noun, verb) =
+        part2(&program, TARGET).ok_or_else(|| anyhow!("no noun and verb produce {}", TARGET))?;
     println!(
         "Part 2: noun: {}, verb: {}, answer: {}",
         noun,
@@ -74,9 +174,34 @@ fn main() -> Result<()> {
 
 #[test]
 fn example() {
-    let mut input = [1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+    let input = [1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+
+    let mut computer = IntcodeComputer::new(&input);
+    computer.run().unwrap();
+
+    assert_eq!(
+        computer.memory().read_range(0, input.len()),
+        [3500, 9, 10, 70, 2, 3, 11, 0, 99, 30, 40, 50]
+    );
+}
+
+#[test]
+fn symbolic_matches_search() {
+    let program = INPUT.parse::<Program>().unwrap();
+    let output = evaluate(&program).unwrap();
+    assert!(matches!(output, Value::Linear(..)));
+
+    let search = iproduct!(0..=99, 0..=99)
+        .find(|&(noun, verb)| run(&program, noun, verb).unwrap() == TARGET);
+    assert_eq!(solve_linear(output, TARGET), Some(search));
 
-    run_intcode(&mut input);
+    // Solving for a target far from the constant term would overflow
+    assert_eq!(solve_linear(Value::Linear(i64::MAX, 0, 1), -5), None);
+    assert_eq!(solve_linear(Value::Linear(0, i64::MAX, 1), -5), None);
+    assert_eq!(solve_linear(Value::Linear(0, 0, -1), i64::MIN), None);
 
-    assert_eq!(input, [3500, 9, 10, 70, 2, 3, 11, 0, 99, 30, 40, 50]);
+    // Reads through the noun and verb as addresses, so the search is used
+    let product = [2, 0, 0, 0, 99];
+    assert_eq!(evaluate(&product), Some(Value::Unknown));
+    assert_eq!(part2(&product, 99 * 99), Some((4, 4)));
 }
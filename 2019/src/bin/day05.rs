@@ -1,4 +1,4 @@
-use advent_of_code_2019::IntcodeComputer;
+use advent_of_code_2019::{IntcodeComputer, Program};
 use anyhow::Result;
 
 const INPUT: &str = include_str!("../../input/day05.txt");
@@ -20,11 +20,7 @@ fn part2(program: Vec<i64>) -> Result<()> {
 }
 
 fn main() -> Result<()> {
-    let program = INPUT
-        .trim()
-        .split(',')
-        .map(str::parse::<i64>)
-        .collect::<Result<Vec<_>, _>>()?;
+    let program = INPUT.parse::<Program>()?.into_words();
 
     part1(program.clone())?;
     part2(program)?;
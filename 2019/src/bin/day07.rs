@@ -1,11 +1,14 @@
-use advent_of_code_2019::{IntcodeComputer, IntcodeError};
+use advent_of_code_2019::{IntcodeComputer, Pipe, Program, RunState};
 use anyhow::Result;
 use itertools::Itertools;
+use std::sync::Arc;
 
 const INPUT: &str = include_str!("../../input/day07.txt");
 
 fn part1(program: &[i64]) -> Result<()> {
-    let mut computer = IntcodeComputer::new(&program);
+    // Resetting only discards the memory written by the previous run
+    let image: Arc<[i64]> = program.into();
+    let mut computer = IntcodeComputer::shared(image.clone());
 
     let max = [0, 1, 2, 3, 4]
         .iter()
@@ -13,7 +16,7 @@ fn part1(program: &[i64]) -> Result<()> {
         .map(|perm| {
             let mut prev_out = 0;
             for i in perm {
-                computer.reset(program);
+                computer.reset(&image);
                 computer.input(*i);
                 computer.input(prev_out);
                 computer.run().unwrap();
@@ -28,33 +31,37 @@ fn part1(program: &[i64]) -> Result<()> {
 }
 
 fn part2(program: &[i64]) -> Result<()> {
-    let mut computer = vec![IntcodeComputer::new(&program); 5];
-
     let max = (5..=9)
-        .into_iter()
         .permutations(5)
         .map(|perm| {
-            perm.into_iter()
-                .zip(computer.iter_mut())
-                .for_each(|(i, c)| {
-                    c.reset(program);
-                    c.input(i);
-                });
+            // pipes[i] feeds amplifier i and is fed by the amplifier before it
+            let pipes = perm
+                .iter()
+                .map(|&phase| {
+                    let pipe = Pipe::new();
+                    pipe.push(phase);
+                    pipe
+                })
+                .collect::<Vec<_>>();
+            pipes[0].push(0);
 
-            let mut prev_out = 0;
-            while computer.iter().fold(false, |acc, c| acc || c.status()) {
-                for c in &mut computer {
-                    c.input(prev_out);
-                    match c.run() {
-                        Ok(()) => {}
-                        Err(IntcodeError::WaitingForInput) => {}
-                        Err(e) => panic!("error: {:?}", e),
-                    }
+            let mut computers = (0..5)
+                .map(|i| {
+                    let mut c = IntcodeComputer::new(program);
+                    c.attach_input(pipes[i].clone());
+                    c.attach_output(pipes[(i + 1) % 5].clone());
+                    c
+                })
+                .collect::<Vec<_>>();
 
-                    prev_out = *c.output().last().unwrap();
+            while computers.iter().any(|c| c.status() != RunState::Halted) {
+                for c in &mut computers {
+                    if let Err(e) = c.run() {
+                        panic!("error: {:?}", e);
+                    }
                 }
             }
-            prev_out
+            pipes[0].pop().unwrap()
         })
         .max()
         .unwrap();
@@ -63,11 +70,7 @@ fn part2(program: &[i64]) -> Result<()> {
 }
 
 fn main() -> Result<()> {
-    let program = INPUT
-        .trim()
-        .split(',')
-        .map(str::parse::<i64>)
-        .collect::<Result<Vec<_>, _>>()?;
+    let program = INPUT.parse::<Program>()?;
 
     part1(&program)?;
     part2(&program)?;
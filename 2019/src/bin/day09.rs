@@ -1,4 +1,4 @@
-use advent_of_code_2019::IntcodeComputer;
+use advent_of_code_2019::{IntcodeComputer, Program};
 use anyhow::Result;
 
 const INPUT: &str = include_str!("../../input/day09.txt");
@@ -20,11 +20,7 @@ fn part2(program: &[i64]) -> Result<()> {
 }
 
 fn main() -> Result<()> {
-    let program = INPUT
-        .trim()
-        .split(',')
-        .map(str::parse::<i64>)
-        .collect::<Result<Vec<_>, _>>()?;
+    let program = INPUT.parse::<Program>()?;
 
     part1(&program)?;
     part2(&program)?;
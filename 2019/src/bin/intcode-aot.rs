@@ -0,0 +1,14 @@
+use advent_of_code_2019::{translate, Program};
+use anyhow::{anyhow, Context, Result};
+use std::env;
+
+/// Translates an Intcode program into a Rust module, written to stdout.
+fn main() -> Result<()> {
+    let path = env::args()
+        .nth(1)
+        .ok_or_else(|| anyhow!("usage: intcode-aot <program>"))?;
+    let program =
+        Program::from_file(&path).with_context(|| format!("unable to load '{}'", path))?;
+    print!("{}", translate(&program));
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use advent_of_code_2019::{AsciiConsole, AsciiOutput, IntcodeComputer, Program, RunState};
+use anyhow::{anyhow, Context, Result};
+use std::env;
+use std::io::{self, BufRead, Write};
+
+/// Runs an ASCII Intcode program interactively, sending each line typed on
+/// stdin as input. Values outside the ASCII range are shown on their own.
+fn main() -> Result<()> {
+    let path = env::args()
+        .nth(1)
+        .ok_or_else(|| anyhow!("usage: intcode-ascii <program>"))?;
+    let program =
+        Program::from_file(&path).with_context(|| format!("unable to load '{}'", path))?;
+    let mut console = AsciiConsole::new(IntcodeComputer::new(&program));
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        let state = console.run();
+        for output in console.take_output() {
+            match output {
+                AsciiOutput::Text(text) => print!("{}", text),
+                AsciiOutput::Value(value) => println!("<{}>", value),
+            }
+        }
+        io::stdout().flush()?;
+
+        match state? {
+            RunState::NeedsInput => match lines.next() {
+                Some(line) => console.send_line(&line?),
+                None => return Ok(()),
+            },
+            RunState::Halted => return Ok(()),
+            state => unreachable!("unexpected state {:?}", state),
+        }
+    }
+}
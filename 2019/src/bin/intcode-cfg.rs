@@ -0,0 +1,17 @@
+use advent_of_code_2019::{ControlFlowGraph, Program};
+use anyhow::{anyhow, Context, Result};
+use std::env;
+use std::io;
+
+/// Writes the control-flow graph of an Intcode program to stdout in Graphviz
+/// DOT format, e.g. `intcode-cfg input/day09.txt | dot -Tsvg > day09.svg`.
+fn main() -> Result<()> {
+    let path = env::args()
+        .nth(1)
+        .ok_or_else(|| anyhow!("usage: intcode-cfg <program>"))?;
+    let program =
+        Program::from_file(&path).with_context(|| format!("unable to load '{}'", path))?;
+    let stdout = io::stdout();
+    ControlFlowGraph::new(&program).write_dot(stdout.lock())?;
+    Ok(())
+}
@@ -0,0 +1,342 @@
+use advent_of_code_2019::{
+    disassemble_with, Access, Disassembled, IntcodeComputer, Program, RunState, Snapshot,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::ops::Range;
+
+const DEFAULT_HISTORY: usize = 100_000;
+
+const HELP: &str = "\
+commands:
+  load <file>           load a program and reset the machine
+  run                   restart the program from the beginning
+  save <file>           write a snapshot of the machine state
+  restore <file>        restore the machine state from a snapshot
+  continue | c          continue until a breakpoint, watchpoint or halt
+  step | s [n]          execute n instructions (default 1)
+  back | bs [n]         undo n instructions (default 1)
+  history <n>           keep at most n instructions of undo history (clears it)
+  break | b <addr>      set a breakpoint
+  delete | d <addr>     remove a breakpoint
+  watch | w <addrs> [r|w|rw]
+                        stop when addrs (a or a..b) are accessed (default w)
+  unwatch <addrs>       remove a watchpoint
+  input | in <v>...     queue input values
+  x <addr> [n]          examine n memory cells (default 1)
+  set <addr> <value>    patch a memory cell
+  list | l [addr] [n]   disassemble n instructions (default: 10 at ip)
+  info | i              show registers, breakpoints and watchpoints
+  help | h              show this message
+  quit | q              exit";
+
+struct Debugger {
+    program: Vec<i64>,
+    computer: IntcodeComputer,
+    history: usize,
+    // Number of outputs already shown to the user
+    shown: usize,
+}
+
+impl Debugger {
+    fn new(program: Vec<i64>) -> Self {
+        let mut computer = IntcodeComputer::new(&program);
+        computer.enable_history(DEFAULT_HISTORY);
+        Self {
+            program,
+            computer,
+            history: DEFAULT_HISTORY,
+            shown: 0,
+        }
+    }
+
+    fn load(&mut self, path: &str) -> Result<()> {
+        self.program = Program::from_file(path)
+            .with_context(|| format!("unable to load '{}'", path))?
+            .into_words();
+        self.computer = IntcodeComputer::new(&self.program);
+        self.computer.enable_history(self.history);
+        self.shown = 0;
+        println!("loaded {} words from {}", self.program.len(), path);
+        Ok(())
+    }
+
+    fn save(&self, path: &str) -> Result<()> {
+        let file =
+            fs::File::create(path).with_context(|| format!("unable to create '{}'", path))?;
+        let mut w = io::BufWriter::new(file);
+        self.computer.snapshot().write_to(&mut w)?;
+        w.flush()?;
+        println!("saved snapshot to {}", path);
+        Ok(())
+    }
+
+    fn restore(&mut self, path: &str) -> Result<()> {
+        let file = fs::File::open(path).with_context(|| format!("unable to read '{}'", path))?;
+        let snapshot = Snapshot::read_from(BufReader::new(file))
+            .with_context(|| format!("invalid snapshot '{}'", path))?;
+        self.computer.restore(&snapshot);
+        self.shown = self.computer.output().len();
+        println!("restored snapshot from {}", path);
+        self.list(self.computer.ip(), 1);
+        Ok(())
+    }
+
+    fn restart(&mut self) {
+        self.computer.reset(&self.program);
+        self.shown = 0;
+    }
+
+    /// Resumes execution, stopping after `steps` instructions if given.
+    fn resume(&mut self, steps: Option<usize>) {
+        match self.computer.status() {
+            RunState::Halted => {
+                println!("program has halted, use `run` to restart it");
+                return;
+            }
+            RunState::Faulted => {
+                println!("program has faulted, use `run` to restart it");
+                return;
+            }
+            _ => {}
+        }
+
+        let result = match steps {
+            Some(n) => self.computer.run_steps(n),
+            None => self.computer.run(),
+        };
+
+        match result {
+            Ok(RunState::Halted) => println!("program halted"),
+            Ok(RunState::Breakpoint(address)) => println!("breakpoint at {}", address),
+            Ok(RunState::NeedsInput) => println!("waiting for input"),
+            Ok(RunState::Watchpoint(hit)) => {
+                let access = match hit.access {
+                    Access::Write => "write",
+                    _ => "read",
+                };
+                println!(
+                    "watchpoint: {} of {} ({} -> {}) by `{}` at {}",
+                    access, hit.address, hit.old, hit.new, hit.instruction, hit.ip
+                );
+            }
+            Ok(RunState::Paused) | Ok(RunState::StepLimit) => {}
+            Ok(state) => println!("stopped: {:?}", state),
+            Err(e) => println!("fault: {}", e),
+        }
+
+        self.show_output();
+        if self.computer.status() != RunState::Halted {
+            self.list(self.computer.ip(), 1);
+        }
+    }
+
+    fn back(&mut self, steps: usize) {
+        let undone = (0..steps).take_while(|_| self.computer.step_back()).count();
+        if undone < steps {
+            println!("reached the start of recorded history");
+        }
+        self.shown = self.shown.min(self.computer.output().len());
+        self.list(self.computer.ip(), 1);
+    }
+
+    fn show_output(&mut self) {
+        for value in &self.computer.output()[self.shown..] {
+            println!("output: {}", value);
+        }
+        self.shown = self.computer.output().len();
+    }
+
+    fn list(&self, start: usize, count: usize) {
+        let memory = self.computer.memory().read_range(start, count * 4);
+        let listing = disassemble_with(&memory, self.computer.custom_opcodes());
+        for item in listing.items().iter().take(count) {
+            let item = Disassembled {
+                address: item.address + start,
+                ..item.clone()
+            };
+            let marker = if item.address == self.computer.ip() {
+                "=>"
+            } else {
+                "  "
+            };
+            println!("{} {}", marker, item);
+        }
+    }
+
+    fn info(&self) {
+        println!("ip: {}", self.computer.ip());
+        println!("relative base: {}", self.computer.relative_base());
+        println!("status: {:?}", self.computer.status());
+        println!(
+            "history: {} of {} instructions",
+            self.computer.history_len(),
+            self.history
+        );
+        let breakpoints = self
+            .computer
+            .breakpoints()
+            .map(|b| b.to_string())
+            .collect::<Vec<_>>();
+        println!("breakpoints: {}", breakpoints.join(", "));
+        let watchpoints = self
+            .computer
+            .watchpoints()
+            .iter()
+            .map(|w| {
+                let access = match w.access {
+                    Access::Read => "r",
+                    Access::Write => "w",
+                    Access::ReadWrite => "rw",
+                };
+                format!("{}..{} ({})", w.addresses.start, w.addresses.end, access)
+            })
+            .collect::<Vec<_>>();
+        println!("watchpoints: {}", watchpoints.join(", "));
+    }
+
+    /// Executes a single command, returning false if the debugger should exit.
+    fn command(&mut self, line: &str) -> Result<bool> {
+        let mut words = line.split_whitespace();
+        let command = match words.next() {
+            Some(command) => command,
+            None => return Ok(true),
+        };
+        let args = words.collect::<Vec<_>>();
+        let arg = |idx: usize| -> Result<&str> {
+            args.get(idx)
+                .copied()
+                .ok_or_else(|| anyhow!("missing argument, see `help`"))
+        };
+        let address = |idx: usize| -> Result<usize> {
+            let a = arg(idx)?;
+            a.parse()
+                .with_context(|| format!("invalid address '{}'", a))
+        };
+
+        match command {
+            "load" => self.load(arg(0)?)?,
+            "run" => {
+                self.restart();
+                self.resume(None);
+            }
+            "save" => self.save(arg(0)?)?,
+            "restore" => self.restore(arg(0)?)?,
+            "continue" | "c" => self.resume(None),
+            "step" | "s" => {
+                let n = if args.is_empty() { 1 } else { address(0)? };
+                self.resume(Some(n));
+            }
+            "back" | "bs" => {
+                let n = if args.is_empty() { 1 } else { address(0)? };
+                self.back(n);
+            }
+            "history" => {
+                self.history = address(0)?;
+                self.computer.enable_history(self.history);
+            }
+            "break" | "b" => self.computer.set_breakpoint(address(0)?),
+            "delete" | "d" => {
+                if !self.computer.clear_breakpoint(address(0)?) {
+                    bail!("no breakpoint at {}", args[0]);
+                }
+            }
+            "watch" | "w" => {
+                let access = match args.get(1).copied() {
+                    None | Some("w") => Access::Write,
+                    Some("r") => Access::Read,
+                    Some("rw") => Access::ReadWrite,
+                    Some(a) => bail!("invalid access '{}', expected r, w or rw", a),
+                };
+                self.computer.watch(parse_range(arg(0)?)?, access);
+            }
+            "unwatch" => {
+                if !self.computer.unwatch(parse_range(arg(0)?)?) {
+                    bail!("no watchpoint on {}", args[0]);
+                }
+            }
+            "input" | "in" => {
+                for a in &args {
+                    let value = a
+                        .parse()
+                        .with_context(|| format!("invalid value '{}'", a))?;
+                    self.computer.input(value);
+                }
+            }
+            "x" => {
+                let start = address(0)?;
+                let count = if args.len() > 1 { address(1)? } else { 1 };
+                for address in start..start + count {
+                    println!("{:05}: {}", address, self.computer.memory().read(address));
+                }
+            }
+            "set" => {
+                let value = arg(1)?
+                    .parse()
+                    .with_context(|| format!("invalid value '{}'", args[1]))?;
+                self.computer.poke(address(0)?, value);
+            }
+            "list" | "l" => {
+                let start = if args.is_empty() {
+                    self.computer.ip()
+                } else {
+                    address(0)?
+                };
+                let count = if args.len() > 1 { address(1)? } else { 10 };
+                self.list(start, count);
+            }
+            "info" | "i" => self.info(),
+            "help" | "h" => println!("{}", HELP),
+            "quit" | "q" => return Ok(false),
+            _ => bail!("unknown command '{}', see `help`", command),
+        }
+
+        Ok(true)
+    }
+}
+
+/// Parses either a single address or a half-open range `start..end`.
+fn parse_range(s: &str) -> Result<Range<usize>> {
+    let parse = |a: &str| -> Result<usize> {
+        a.parse()
+            .with_context(|| format!("invalid address '{}'", a))
+    };
+    let mut bounds = s.splitn(2, "..");
+    let start = parse(bounds.next().unwrap_or_default())?;
+    let end = match bounds.next() {
+        Some(end) => parse(end)?,
+        None => start + 1,
+    };
+    if end <= start {
+        bail!("empty address range '{}'", s);
+    }
+    Ok(start..end)
+}
+
+fn main() -> Result<()> {
+    let mut debugger = Debugger::new(Vec::new());
+    if let Some(path) = env::args().nth(1) {
+        debugger.load(&path)?;
+    }
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("(icdb) ");
+        io::stdout().flush()?;
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
+        match debugger.command(&line) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => println!("error: {:#}", e),
+        }
+    }
+
+    Ok(())
+}
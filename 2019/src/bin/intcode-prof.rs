@@ -0,0 +1,41 @@
+use advent_of_code_2019::{IntcodeComputer, Program, RunState};
+use anyhow::{anyhow, bail, Context, Result};
+use std::env;
+use std::io;
+
+const HOT_SPOTS: usize = 20;
+
+/// Runs an Intcode program with the given inputs and prints a profile of
+/// where it spent its time.
+fn main() -> Result<()> {
+    let mut args = env::args().skip(1);
+    let path = args
+        .next()
+        .ok_or_else(|| anyhow!("usage: intcode-prof <program> [input]..."))?;
+    let program =
+        Program::from_file(&path).with_context(|| format!("unable to load '{}'", path))?;
+
+    let mut computer = IntcodeComputer::new(&program);
+    for arg in args {
+        let value = arg
+            .parse()
+            .with_context(|| format!("invalid input '{}'", arg))?;
+        computer.input(value);
+    }
+
+    computer.start_profile();
+    let state = computer.run();
+    for value in computer.output() {
+        println!("output: {}", value);
+    }
+    match state? {
+        RunState::Halted => {}
+        RunState::NeedsInput => bail!("program needs more input"),
+        state => bail!("program stopped: {:?}", state),
+    }
+
+    println!();
+    let profile = computer.take_profile().unwrap();
+    profile.write_report(io::stdout().lock(), computer.memory(), HOT_SPOTS)?;
+    Ok(())
+}
@@ -1,202 +1,372 @@
-use std::collections::VecDeque;
+use std::array;
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt;
 use std::iter;
 use thiserror::Error;
 
+use cache::{DecodeCache, FastLoop};
+use custom::OpcodeTable;
+use history::{Change, History};
+use limits::Limits;
+use std::ops::{Deref, Range};
+use std::sync::Arc;
+use std::time::Duration;
+use watch::Accesses;
+
+mod aot;
+mod arithmetic;
+mod ascii;
+mod builder;
+mod cache;
+mod cfg;
+mod custom;
+mod history;
+mod io;
+mod limits;
+mod memory;
+mod profile;
+mod program;
+mod snapshot;
+mod trace;
+mod watch;
+
+pub use aot::{translate, AotMachine};
+pub use arithmetic::Arithmetic;
+pub use ascii::{decode_ascii, AsciiConsole, AsciiOutput};
+pub use builder::Builder;
+pub use cache::Engine;
+pub use cfg::{BasicBlock, ControlFlowGraph, Successor};
+pub use custom::{CustomOpcode, Handler};
+pub use io::{InputSource, IterInput, OutputSink, Pipe};
+pub use memory::{CowMemory, DenseMemory, Memory, PagedMemory};
+pub use profile::Profile;
+pub use program::{Program, ProgramError};
+pub use snapshot::{Snapshot, SnapshotError};
+pub use trace::{Trace, TraceEntry};
+pub use watch::{Access, WatchHit, Watchpoint};
+
 pub type Result<T, E = IntcodeError> = ::std::result::Result<T, E>;
 
-const MEMORY_SIZE: usize = 1 << 16;
-#[derive(Clone)]
 pub struct IntcodeComputer {
-    tape: [i64; MEMORY_SIZE],
+    memory: Box<dyn Memory>,
     input: VecDeque<i64>,
     output: Vec<i64>,
+    input_source: Option<Box<dyn InputSource>>,
+    output_sink: Option<Box<dyn OutputSink>>,
     ip: usize,
     offset: usize,
     relative_base: i64,
-    status: bool,
+    state: RunState,
+    breakpoints: BTreeSet<usize>,
+    watchpoints: Vec<Watchpoint>,
+    trace: Option<Trace>,
+    profile: Option<Profile>,
+    history: Option<History>,
+    cache: Option<DecodeCache>,
+    budget: Option<u64>,
+    timeout: Option<Duration>,
+    arithmetic: Arithmetic,
+    opcodes: OpcodeTable,
 }
 
 impl IntcodeComputer {
     pub fn new(program: &[i64]) -> Self {
-        if program.len() > MEMORY_SIZE {
-            panic!("program too big");
-        }
+        Self::with_memory(program, DenseMemory::default())
+    }
 
-        let mut tape = [0; MEMORY_SIZE];
-        tape[..program.len()].copy_from_slice(program);
+    /// Creates a computer backed by the provided memory implementation.
+    pub fn with_memory(program: &[i64], memory: impl Memory + 'static) -> Self {
+        Self::with_boxed_memory(program, Box::new(memory))
+    }
+
+    /// Creates a computer sharing `image` with any others created from it,
+    /// which only copies the memory it writes.
+    ///
+    /// Resetting with the same image is then proportional to the memory
+    /// written since the last reset.
+    pub fn shared(image: Arc<[i64]>) -> Self {
+        let memory = CowMemory::new(image.clone());
+        Self::with_memory(&image, memory)
+    }
+
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    fn with_boxed_memory(program: &[i64], mut memory: Box<dyn Memory>) -> Self {
+        memory.load(program);
 
         Self {
-            tape,
+            memory,
             input: VecDeque::new(),
             output: Vec::new(),
+            input_source: None,
+            output_sink: None,
             ip: 0,
             offset: 0,
             relative_base: 0,
-            status: true,
+            state: RunState::Paused,
+            breakpoints: BTreeSet::new(),
+            watchpoints: Vec::new(),
+            trace: None,
+            profile: None,
+            history: None,
+            cache: None,
+            budget: None,
+            timeout: None,
+            arithmetic: Arithmetic::default(),
+            opcodes: OpcodeTable::default(),
+        }
+    }
+}
+
+/// Attached input sources and output sinks can't be duplicated, so a clone
+/// starts out detached and uses its own input queue and output buffer.
+impl Clone for IntcodeComputer {
+    fn clone(&self) -> Self {
+        Self {
+            memory: self.memory.clone(),
+            input: self.input.clone(),
+            output: self.output.clone(),
+            input_source: None,
+            output_sink: None,
+            ip: self.ip,
+            offset: self.offset,
+            relative_base: self.relative_base,
+            state: self.state,
+            breakpoints: self.breakpoints.clone(),
+            watchpoints: self.watchpoints.clone(),
+            trace: self.trace.clone(),
+            profile: self.profile.clone(),
+            history: self.history.clone(),
+            cache: self.cache.clone(),
+            budget: self.budget,
+            timeout: self.timeout,
+            arithmetic: self.arithmetic,
+            opcodes: self.opcodes.clone(),
         }
     }
 }
 
 impl IntcodeComputer {
-    fn fetch(&mut self) -> Result<i64> {
-        let next = self.memread(self.ip + self.offset)?;
-        self.offset += 1;
-        Ok(next)
-    }
-
-    fn fetch_operand(&mut self, param_mode: ParameterMode) -> Result<Operand> {
-        let param = self.fetch()?;
-        let operand = match param_mode {
-            ParameterMode::Position => Operand::Position(param as usize),
-            ParameterMode::Immediate => Operand::Immediate(param),
-            ParameterMode::Relative => Operand::Relative(param),
-        };
-        Ok(operand)
+    fn fault_context(&self) -> FaultContext {
+        let word = self.memread(self.ip);
+        FaultContext {
+            ip: self.ip,
+            word,
+            opcode: Opcode::from_code(word % 100)
+                .or_else(|| self.opcodes.get(word % 100).map(Opcode::Custom)),
+            relative_base: self.relative_base,
+        }
     }
 
-    fn memread(&self, address: usize) -> Result<i64> {
-        self.tape
-            .get(address)
-            .copied()
-            .ok_or(IntcodeError::AddressOutOfBound(address))
+    fn overflow(&self) -> IntcodeError {
+        IntcodeError::Overflow {
+            context: self.fault_context(),
+        }
     }
 
-    fn memwrite(&mut self, address: usize, value: i64) -> Result<()> {
-        *self
-            .tape
-            .get_mut(address)
-            .ok_or(IntcodeError::AddressOutOfBound(address))? = value;
+    fn adjust_relative_base(&mut self, delta: i64) -> Result<()> {
+        self.relative_base = self
+            .arithmetic
+            .add(self.relative_base, delta)
+            .ok_or_else(|| self.overflow())?;
         Ok(())
     }
 
+    fn address(&self, address: i64) -> Result<usize> {
+        if address < 0 {
+            Err(IntcodeError::NegativeAddress {
+                address,
+                context: self.fault_context(),
+            })
+        } else {
+            Ok(address as usize)
+        }
+    }
+
+    fn memread(&self, address: usize) -> i64 {
+        self.memory.read(address)
+    }
+
+    fn memwrite(&mut self, address: usize, value: i64) {
+        self.memory.write(address, value);
+        if let Some(cache) = self.cache.as_mut() {
+            cache.invalidate(address);
+        }
+    }
+
     fn read_operand(&self, operand: Operand) -> Result<i64> {
-        let operand = match operand {
-            Operand::Position(addr) => self.memread(addr)?,
-            Operand::Immediate(operand) => operand,
-            Operand::Relative(offset) => self.memread((self.relative_base + offset) as usize)?,
-        };
-        Ok(operand)
+        match self.operand_address(operand)? {
+            Some(address) => Ok(self.memread(address)),
+            None => Ok(operand.value()),
+        }
     }
 
     fn write_operand(&mut self, operand: Operand, value: i64) -> Result<()> {
-        match operand {
-            Operand::Position(addr) => self.memwrite(addr, value)?,
-            Operand::Immediate(_) => panic!("Invalid operand"),
-            Operand::Relative(offset) => {
-                self.memwrite((self.relative_base + offset) as usize, value)?
+        match self.operand_address(operand)? {
+            Some(address) => self.memwrite(address, value),
+            None => {
+                return Err(IntcodeError::WriteToImmediate {
+                    context: self.fault_context(),
+                })
             }
         };
         Ok(())
     }
 
-    fn decode(i: i64) -> Result<(Opcode, ParameterMode, ParameterMode, ParameterMode)> {
-        if i >= 100_000 {
-            return Err(IntcodeError::InvalidInstruction(i));
+    /// The address `operand` refers to, or `None` if it's immediate. Relative
+    /// addresses which overflow fault rather than wrapping.
+    fn operand_address(&self, operand: Operand) -> Result<Option<usize>> {
+        match operand {
+            Operand::Position(addr) => Ok(Some(self.address(addr)?)),
+            Operand::Immediate(_) => Ok(None),
+            Operand::Relative(offset) => {
+                let address = self
+                    .relative_base
+                    .checked_add(offset)
+                    .ok_or_else(|| self.overflow())?;
+                Ok(Some(self.address(address)?))
+            }
         }
+    }
 
-        let opcode = match i % 100 {
-            1 => Opcode::Add,
-            2 => Opcode::Mul,
-            3 => Opcode::Input,
-            4 => Opcode::Output,
-            5 => Opcode::JumpIfTrue,
-            6 => Opcode::JumpIfFalse,
-            7 => Opcode::LessThan,
-            8 => Opcode::Equals,
-            9 => Opcode::AdjustRelativeBase,
-            99 => Opcode::Halt,
-            _ => return Err(IntcodeError::InvalidInstruction(i)),
+    /// The memory `instruction` accesses when executed, captured before it
+    /// runs. The target of a jump which isn't taken isn't read.
+    ///
+    /// Operands which can't be accessed are left out, as the instruction then
+    /// either faults or blocks on input without using them.
+    fn accesses(&self, instruction: Instruction) -> Accesses {
+        let mut sources = instruction.sources();
+        let taken = match instruction {
+            Instruction::JumpIfTrue(rs, _) => self.read_operand(rs).map(|v| v != 0),
+            Instruction::JumpIfFalse(rs, _) => self.read_operand(rs).map(|v| v == 0),
+            _ => Ok(true),
         };
-        let mut operand_types = [ParameterMode::Position; 3];
-
-        for (idx, op_type) in iter::successors(Some(i / 100), |n| Some(n / 10))
-            .take(3)
-            .map(|n| match n % 10 {
-                0 => Ok(ParameterMode::Position),
-                1 => Ok(ParameterMode::Immediate),
-                2 => Ok(ParameterMode::Relative),
-                _ => Err(IntcodeError::InvalidInstruction(i)),
+        if taken != Ok(true) {
+            sources.len = 1;
+        }
+
+        let reads = sources
+            .into_iter()
+            .filter_map(|operand| {
+                let address = self.operand_address(operand).ok()?;
+                let value = address.map_or(operand.value(), |a| self.memread(a));
+                Some((address, value))
             })
-            .enumerate()
-        {
-            operand_types[idx] = op_type?;
+            .collect();
+        let write = instruction
+            .destination()
+            .and_then(|operand| self.operand_address(operand).ok().flatten())
+            .map(|address| (address, self.memread(address)));
+
+        Accesses {
+            ip: self.ip,
+            reads,
+            write,
         }
+    }
 
-        Ok((opcode, operand_types[0], operand_types[1], operand_types[2]))
+    /// Starts a trace entry for `instruction`, which is completed by
+    /// `finish_trace_entry` once it has executed.
+    fn start_trace_entry(&self, instruction: Instruction, accesses: &Accesses) -> TraceEntry {
+        TraceEntry {
+            ip: self.ip,
+            relative_base: self.relative_base,
+            instruction,
+            reads: accesses.reads.iter().map(|&(_, value)| value).collect(),
+            write: accesses.write.map(|(address, _)| (address, 0)),
+        }
     }
 
-    fn fetch_and_decode(&mut self) -> Result<Instruction> {
-        use ParameterMode::*;
+    fn finish_trace_entry(&mut self, mut entry: TraceEntry) {
+        if let Some((address, value)) = entry.write.as_mut() {
+            *value = self.memread(*address);
+        }
+        if let Some(trace) = self.trace.as_mut() {
+            trace.push(entry);
+        }
+    }
 
-        let inst = self.fetch()?;
-        let instruction = match Self::decode(inst)? {
-            (Opcode::Add, p1, p2, p3) => Instruction::Add(
-                self.fetch_operand(p1)?,
-                self.fetch_operand(p2)?,
-                self.fetch_operand(p3)?,
-            ),
-            (Opcode::Mul, p1, p2, p3) => Instruction::Mul(
-                self.fetch_operand(p1)?,
-                self.fetch_operand(p2)?,
-                self.fetch_operand(p3)?,
-            ),
-            (Opcode::Input, p1, Position, Position) => Instruction::Input(self.fetch_operand(p1)?),
-            (Opcode::Output, p1, Position, Position) => {
-                Instruction::Output(self.fetch_operand(p1)?)
-            }
-            (Opcode::JumpIfTrue, p1, p2, Position) => {
-                Instruction::JumpIfTrue(self.fetch_operand(p1)?, self.fetch_operand(p2)?)
-            }
-            (Opcode::JumpIfFalse, p1, p2, Position) => {
-                Instruction::JumpIfFalse(self.fetch_operand(p1)?, self.fetch_operand(p2)?)
-            }
-            (Opcode::LessThan, p1, p2, p3) => Instruction::LessThan(
-                self.fetch_operand(p1)?,
-                self.fetch_operand(p2)?,
-                self.fetch_operand(p3)?,
-            ),
-            (Opcode::Equals, p1, p2, p3) => Instruction::Equals(
-                self.fetch_operand(p1)?,
-                self.fetch_operand(p2)?,
-                self.fetch_operand(p3)?,
-            ),
-            (Opcode::AdjustRelativeBase, p1, Position, Position) => {
-                Instruction::AdjustRelativeBase(self.fetch_operand(p1)?)
-            }
-            (Opcode::Halt, Position, Position, Position) => Instruction::Halt,
-            (_, _, _, _) => return Err(IntcodeError::InvalidInstruction(inst)),
-        };
+    fn start_change(&self, accesses: &Accesses) -> Change {
+        Change {
+            ip: self.ip,
+            relative_base: self.relative_base,
+            state: self.state,
+            write: accesses.write,
+            input: None,
+            output_len: self.output.len(),
+        }
+    }
+
+    fn finish_change(&mut self, mut change: Change, instruction: Instruction) {
+        if let (Instruction::Input(_), Some((address, _))) = (instruction, change.write) {
+            change.input = Some(self.memread(address));
+        }
+        if let Some(history) = self.history.as_mut() {
+            history.push(change);
+        }
+    }
 
+    fn fetch_and_decode(&mut self) -> Result<Instruction> {
+        let read = |address| Some(self.memread(address));
+        let instruction = Instruction::decode_with(self.ip, read, self.opcodes.opcodes())
+            .ok_or_else(|| IntcodeError::InvalidInstruction {
+                context: self.fault_context(),
+            })?;
+        self.offset = instruction.size();
         Ok(instruction)
     }
 
-    fn execute(&mut self, instruction: Instruction) -> Result<()> {
+    fn next_input(&mut self) -> Option<i64> {
+        match self.input.pop_front() {
+            Some(input) => Some(input),
+            None => self
+                .input_source
+                .as_mut()
+                .and_then(|source| source.next_input()),
+        }
+    }
+
+    fn emit(&mut self, value: i64) {
+        match self.output_sink.as_mut() {
+            Some(sink) => sink.emit(value),
+            None => self.output.push(value),
+        }
+    }
+
+    /// Executes `instruction`, returning false if it's blocked on input.
+    fn execute(&mut self, instruction: Instruction) -> Result<bool> {
         use Instruction::*;
 
         match instruction {
             Add(rs, rt, rd) => {
-                self.write_operand(rd, self.read_operand(rs)? + self.read_operand(rt)?)?
+                let (a, b) = (self.read_operand(rs)?, self.read_operand(rt)?);
+                let sum = self.arithmetic.add(a, b).ok_or_else(|| self.overflow())?;
+                self.write_operand(rd, sum)?
             }
             Mul(rs, rt, rd) => {
-                self.write_operand(rd, self.read_operand(rs)? * self.read_operand(rt)?)?
+                let (a, b) = (self.read_operand(rs)?, self.read_operand(rt)?);
+                let product = self.arithmetic.mul(a, b).ok_or_else(|| self.overflow())?;
+                self.write_operand(rd, product)?
             }
-            Input(rd) => {
-                let input = self
-                    .input
-                    .pop_front()
-                    .ok_or(IntcodeError::WaitingForInput)?;
-                self.write_operand(rd, input)?;
+            Input(rd) => match self.next_input() {
+                Some(input) => self.write_operand(rd, input)?,
+                None => return Ok(false),
+            },
+            Output(rs) => {
+                let value = self.read_operand(rs)?;
+                self.emit(value);
             }
-            Output(rs) => self.output.push(self.read_operand(rs)?),
             JumpIfTrue(rs, rt) => {
                 if self.read_operand(rs)? != 0 {
-                    self.ip = self.read_operand(rt)? as usize;
+                    self.ip = self.address(self.read_operand(rt)?)?;
                     self.offset = 0;
                 }
             }
             JumpIfFalse(rs, rt) => {
                 if self.read_operand(rs)? == 0 {
-                    self.ip = self.read_operand(rt)? as usize;
+                    self.ip = self.address(self.read_operand(rt)?)?;
                     self.offset = 0;
                 }
             }
@@ -207,11 +377,26 @@ impl IntcodeComputer {
                 rd,
                 (self.read_operand(rs)? == self.read_operand(rt)?).into(),
             )?,
-            AdjustRelativeBase(rs) => self.relative_base += self.read_operand(rs)?,
-            Halt => self.status = false,
+            AdjustRelativeBase(rs) => self.adjust_relative_base(self.read_operand(rs)?)?,
+            Halt => self.state = RunState::Halted,
+            Custom(opcode, _) => {
+                let args = instruction
+                    .sources()
+                    .into_iter()
+                    .map(|operand| self.read_operand(operand))
+                    .collect::<Result<Vec<_>>>()?;
+                let handler = self.opcodes.handler(opcode.code);
+                let value = handler(&args).map_err(|message| IntcodeError::Handler {
+                    message,
+                    context: self.fault_context(),
+                })?;
+                if let Some(rd) = instruction.destination() {
+                    self.write_operand(rd, value)?;
+                }
+            }
         };
 
-        Ok(())
+        Ok(true)
     }
 
     fn commit(&mut self) {
@@ -219,46 +404,486 @@ impl IntcodeComputer {
         self.offset = 0;
     }
 
-    pub fn run(&mut self) -> Result<()> {
+    /// Executes a single instruction, returning it, or `None` if the machine
+    /// is halted, faulted or the instruction is blocked on input.
+    pub fn step(&mut self) -> Result<Option<Instruction>> {
+        if self.stopped() {
+            return Ok(None);
+        }
+
+        let result = self.step_unchecked();
+        self.record_fault(result)
+    }
+
+    fn step_unchecked(&mut self) -> Result<Option<Instruction>> {
         self.offset = 0;
+        let ip = self.ip;
+        let instruction = self.fetch_and_decode()?;
+        let accesses =
+            if self.trace.is_some() || self.history.is_some() || !self.watchpoints.is_empty() {
+                Some(self.accesses(instruction))
+            } else {
+                None
+            };
+        let entry = match (&self.trace, &accesses) {
+            (Some(_), Some(accesses)) => Some(self.start_trace_entry(instruction, accesses)),
+            _ => None,
+        };
+        let change = match (&self.history, &accesses) {
+            (Some(_), Some(accesses)) => Some(self.start_change(accesses)),
+            _ => None,
+        };
+        if !self.execute(instruction)? {
+            self.state = RunState::NeedsInput;
+            return Ok(None);
+        }
+        if let Some(entry) = entry {
+            self.finish_trace_entry(entry);
+        }
+        if let Some(change) = change {
+            self.finish_change(change, instruction);
+        }
+        if let Some(profile) = self.profile.as_mut() {
+            profile.record(ip, instruction);
+        }
+        let hit = accesses.and_then(|accesses| {
+            accesses.check(&self.watchpoints, instruction, |a| self.memread(a))
+        });
+        self.commit();
+        if self.state != RunState::Halted {
+            self.state = match hit {
+                Some(hit) => RunState::Watchpoint(hit),
+                None => RunState::Paused,
+            };
+        }
+        Ok(Some(instruction))
+    }
+
+    /// Undoes the most recently executed instruction, returning false if
+    /// there's no recorded history left to undo.
+    ///
+    /// Consumed input is returned to the front of the input queue and
+    /// buffered output is discarded, but values already delivered to an
+    /// attached output sink can't be taken back.
+    pub fn step_back(&mut self) -> bool {
+        let change = match self.history.as_mut().and_then(History::pop) {
+            Some(change) => change,
+            None => return false,
+        };
+
+        if let Some((address, value)) = change.write {
+            self.memwrite(address, value);
+        }
+        if let Some(value) = change.input {
+            self.input.push_front(value);
+        }
+        self.output.truncate(change.output_len);
+        self.ip = change.ip;
+        self.relative_base = change.relative_base;
+        self.state = change.state;
+        true
+    }
 
-        while self.status {
+    /// Starts recording an undo log for `step_back`, keeping only the most
+    /// recent `budget` instructions.
+    pub fn enable_history(&mut self, budget: usize) {
+        self.history = Some(History::new(budget));
+    }
+
+    pub fn disable_history(&mut self) {
+        self.history = None;
+    }
+
+    /// Number of instructions which can currently be undone.
+    pub fn history_len(&self) -> usize {
+        self.history.as_ref().map_or(0, History::len)
+    }
+
+    /// Breakpoints and `stop` are checked before every instruction, except
+    /// that the breakpoint the machine is stopped at is skipped so that
+    /// resuming from it makes progress.
+    fn run_with(
+        &mut self,
+        mut stop: impl FnMut(&Self) -> bool,
+        mut steps: Option<usize>,
+        pause_on_output: bool,
+    ) -> Result<RunState> {
+        let mut limits = Limits::start(self.budget, self.timeout);
+        let mut resuming = self.state == RunState::Breakpoint(self.ip);
+        let state = loop {
+            if self.stopped() {
+                break self.state;
+            }
+            if stop(self) {
+                break RunState::Paused;
+            }
+            if !resuming && self.breakpoints.contains(&self.ip) {
+                break RunState::Breakpoint(self.ip);
+            }
+            if steps == Some(0) {
+                break RunState::StepLimit;
+            }
+            if let Some(state) = limits.exceeded() {
+                break state;
+            }
+            resuming = false;
+
+            match self.step()? {
+                None => break self.state,
+                Some(_) if matches!(self.state, RunState::Watchpoint(_)) => break self.state,
+                Some(Instruction::Output(rs)) if pause_on_output => {
+                    break RunState::Output(self.read_operand(rs)?)
+                }
+                Some(_) => {}
+            }
+            if let Some(n) = steps.as_mut() {
+                *n -= 1;
+            }
+            limits.retire();
+        };
+
+        self.state = state;
+        Ok(state)
+    }
+
+    /// The loop for when nothing needs to observe individual instructions,
+    /// skipping the per-step bookkeeping done by `step`.
+    fn run_fast(&mut self, pause_on_output: bool) -> Result<RunState> {
+        let result = self.run_fast_unchecked(pause_on_output);
+        self.record_fault(result)
+    }
+
+    /// With the cached engine, cached instructions run in bursts, leaving
+    /// only those `FastLoop` can't handle to be interpreted here.
+    fn run_fast_unchecked(&mut self, pause_on_output: bool) -> Result<RunState> {
+        let mut limits = Limits::start(self.budget, self.timeout);
+        let state = loop {
+            if self.stopped() {
+                break self.state;
+            }
+            if let Some(state) = self.run_cached(&mut limits, pause_on_output) {
+                break state;
+            }
+            if let Some(state) = limits.exceeded() {
+                break state;
+            }
+
+            self.offset = 0;
             let instruction = self.fetch_and_decode()?;
-            self.execute(instruction)?;
+            if !self.execute(instruction)? {
+                break RunState::NeedsInput;
+            }
             self.commit();
+            limits.retire();
+            if self.state != RunState::Halted {
+                self.state = RunState::Paused;
+            }
+            match instruction {
+                Instruction::Output(rs) if pause_on_output => {
+                    break RunState::Output(self.read_operand(rs)?)
+                }
+                _ => {}
+            }
+        };
+
+        self.state = state;
+        Ok(state)
+    }
+
+    /// Runs cached instructions until one needs interpreting, returning the
+    /// state to stop in if the run ends first.
+    fn run_cached(&mut self, limits: &mut Limits, pause_on_output: bool) -> Option<RunState> {
+        let cache = self.cache.as_mut()?;
+        let mut fast = FastLoop {
+            cells: self.memory.cells_mut()?,
+            cache,
+            custom: self.opcodes.opcodes(),
+            input: &mut self.input,
+            output: &mut self.output,
+            sink: self.output_sink.as_mut(),
+            arithmetic: self.arithmetic,
+            ip: self.ip,
+            relative_base: self.relative_base,
+        };
+        let state = fast.run(limits, pause_on_output);
+        let (ip, relative_base) = (fast.ip, fast.relative_base);
+        self.ip = ip;
+        self.relative_base = relative_base;
+        state
+    }
+
+    /// True if the machine can't run again until it's reset or restored.
+    fn stopped(&self) -> bool {
+        matches!(self.state, RunState::Halted | RunState::Faulted)
+    }
+
+    fn record_fault<T>(&mut self, result: Result<T>) -> Result<T> {
+        if result.is_err() {
+            self.state = RunState::Faulted;
         }
-        Ok(())
+        result
+    }
+
+    fn observed(&self) -> bool {
+        self.trace.is_some()
+            || self.history.is_some()
+            || self.profile.is_some()
+            || !self.watchpoints.is_empty()
+            || !self.breakpoints.is_empty()
+    }
+
+    /// Runs until the program halts, needs input or reaches a breakpoint.
+    pub fn run(&mut self) -> Result<RunState> {
+        if !self.observed() {
+            return self.run_fast(false);
+        }
+        self.run_with(|_| false, None, false)
+    }
+
+    /// Like `run`, but also pauses as soon as `stop` returns true.
+    pub fn run_until(&mut self, stop: impl FnMut(&Self) -> bool) -> Result<RunState> {
+        self.run_with(stop, None, false)
+    }
+
+    /// Like `run`, but executes at most `steps` instructions.
+    pub fn run_steps(&mut self, steps: usize) -> Result<RunState> {
+        self.run_with(|_| false, Some(steps), false)
+    }
+
+    /// Like `run`, but also stops right after each value is output.
+    pub fn run_to_output(&mut self) -> Result<RunState> {
+        if !self.observed() {
+            return self.run_fast(true);
+        }
+        self.run_with(|_| false, None, true)
+    }
+
+    /// Limits every subsequent run to executing at most `budget`
+    /// instructions, after which it stops with `RunState::BudgetExhausted`.
+    /// Running again resumes the program with a fresh budget.
+    pub fn set_budget(&mut self, budget: Option<u64>) {
+        self.budget = budget;
+    }
+
+    /// Stops every subsequent run with `RunState::TimedOut` once it has
+    /// taken longer than `timeout`. Running again resumes the program.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    pub fn set_arithmetic(&mut self, arithmetic: Arithmetic) {
+        self.arithmetic = arithmetic;
+    }
+
+    pub fn arithmetic(&self) -> Arithmetic {
+        self.arithmetic
+    }
+
+    pub fn set_breakpoint(&mut self, address: usize) {
+        self.breakpoints.insert(address);
+    }
+
+    pub fn clear_breakpoint(&mut self, address: usize) -> bool {
+        self.breakpoints.remove(&address)
+    }
+
+    pub fn breakpoints(&self) -> impl Iterator<Item = usize> + '_ {
+        self.breakpoints.iter().copied()
+    }
+
+    /// Stops execution after any instruction which accesses `addresses`.
+    pub fn watch(&mut self, addresses: Range<usize>, access: Access) {
+        self.watchpoints.push(Watchpoint { addresses, access });
+    }
+
+    /// Removes every watchpoint on exactly `addresses`.
+    pub fn unwatch(&mut self, addresses: Range<usize>) -> bool {
+        let len = self.watchpoints.len();
+        self.watchpoints.retain(|w| w.addresses != addresses);
+        self.watchpoints.len() != len
+    }
+
+    pub fn watchpoints(&self) -> &[Watchpoint] {
+        &self.watchpoints
+    }
+
+    /// The opcodes registered with `Builder::opcode`.
+    pub fn custom_opcodes(&self) -> &[CustomOpcode] {
+        self.opcodes.opcodes()
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    pub fn memory(&self) -> &dyn Memory {
+        &*self.memory
+    }
+
+    /// Overwrites a single memory cell, e.g. to patch a running program.
+    pub fn poke(&mut self, address: usize, value: i64) {
+        self.memwrite(address, value)
     }
 
     pub fn input(&mut self, val: i64) {
         self.input.push_back(val)
     }
 
+    /// Output produced while no output sink was attached.
     pub fn output(&self) -> &[i64] {
         &self.output
     }
 
+    /// Reads input from `source` once the queue fed by `input` is empty.
+    pub fn attach_input(&mut self, source: impl InputSource + 'static) {
+        self.input_source = Some(Box::new(source));
+    }
+
+    /// Sends all further output to `sink` instead of the output buffer.
+    pub fn attach_output(&mut self, sink: impl OutputSink + 'static) {
+        self.output_sink = Some(Box::new(sink));
+    }
+
+    pub fn detach_input(&mut self) -> Option<Box<dyn InputSource>> {
+        self.input_source.take()
+    }
+
+    pub fn detach_output(&mut self) -> Option<Box<dyn OutputSink>> {
+        self.output_sink.take()
+    }
+
+    /// Starts recording every executed instruction, discarding any previous trace.
+    pub fn start_trace(&mut self) {
+        self.trace = Some(Trace::default());
+    }
+
+    pub fn trace(&self) -> Option<&Trace> {
+        self.trace.as_ref()
+    }
+
+    /// Stops tracing, returning everything recorded since `start_trace`.
+    pub fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
+
+    /// Starts counting executed instructions, discarding any previous profile.
+    pub fn start_profile(&mut self) {
+        self.profile = Some(Profile::default());
+    }
+
+    pub fn profile(&self) -> Option<&Profile> {
+        self.profile.as_ref()
+    }
+
+    pub fn take_profile(&mut self) -> Option<Profile> {
+        self.profile.take()
+    }
+
+    /// Selects how instructions are fetched and decoded. Both engines behave
+    /// identically apart from speed.
+    pub fn set_engine(&mut self, engine: Engine) {
+        self.cache = match engine {
+            Engine::Interpreter => None,
+            Engine::Cached => Some(DecodeCache::default()),
+        };
+    }
+
+    pub fn engine(&self) -> Engine {
+        match self.cache {
+            Some(_) => Engine::Cached,
+            None => Engine::Interpreter,
+        }
+    }
+
     pub fn reset(&mut self, program: &[i64]) {
-        let (p, zeros) = self.tape.split_at_mut(program.len());
-        p.copy_from_slice(program);
-        for i in zeros {
-            *i = 0;
+        self.memory.load(program);
+        if let Some(cache) = self.cache.as_mut() {
+            cache.clear();
         }
 
         self.ip = 0;
         self.input.clear();
         self.output.clear();
         self.relative_base = 0;
-        self.status = true;
+        self.state = RunState::Paused;
+        if let Some(history) = self.history.as_mut() {
+            history.clear();
+        }
     }
 
-    pub fn status(&self) -> bool {
-        self.status
+    /// Captures the machine state, excluding breakpoints, traces and any
+    /// attached input sources or output sinks.
+    pub fn snapshot(&self) -> Snapshot {
+        let memory = snapshot::compact(self.memory.segments());
+
+        Snapshot {
+            ip: self.ip,
+            relative_base: self.relative_base,
+            status: self.state,
+            input: self.input.iter().copied().collect(),
+            output: self.output.clone(),
+            memory,
+        }
+    }
+
+    /// Replaces the machine state with `snapshot`, keeping the current memory
+    /// implementation, breakpoints and attached I/O.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.memory.load(&[]);
+        if let Some(cache) = self.cache.as_mut() {
+            cache.clear();
+        }
+        for (start, cells) in &snapshot.memory {
+            for (idx, &value) in cells.iter().enumerate() {
+                self.memory.write(start + idx, value);
+            }
+        }
+
+        self.ip = snapshot.ip;
+        self.relative_base = snapshot.relative_base;
+        self.state = snapshot.status;
+        self.input = snapshot.input.iter().copied().collect();
+        self.output = snapshot.output.clone();
+        if let Some(history) = self.history.as_mut() {
+            history.clear();
+        }
+    }
+
+    /// The state the machine was left in by the last run or step.
+    pub fn status(&self) -> RunState {
+        self.state
     }
 }
 
-#[derive(Clone, Copy)]
-enum Opcode {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunState {
+    /// Stopped at an arbitrary point; running again resumes the program.
+    Paused,
+    Halted,
+    /// Blocked on an input instruction until more input is provided.
+    NeedsInput,
+    /// Stopped before executing the instruction at a breakpoint.
+    Breakpoint(usize),
+    StepLimit,
+    /// Stopped after running out of the instruction budget.
+    BudgetExhausted,
+    /// Stopped after running for longer than the timeout.
+    TimedOut,
+    /// Stopped right after outputting a value.
+    Output(i64),
+    /// Stopped right after an instruction accessed a watched address.
+    Watchpoint(WatchHit),
+    /// Stopped at an instruction which faulted. Running again does nothing
+    /// until the machine is reset or restored.
+    Faulted,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Opcode {
     Add,
     Mul,
     Input,
@@ -269,10 +894,104 @@ enum Opcode {
     Equals,
     AdjustRelativeBase,
     Halt,
+    /// An opcode added with `Builder::opcode`.
+    Custom(CustomOpcode),
+}
+
+impl Opcode {
+    const ALL: [Opcode; 10] = [
+        Opcode::Add,
+        Opcode::Mul,
+        Opcode::Input,
+        Opcode::Output,
+        Opcode::JumpIfTrue,
+        Opcode::JumpIfFalse,
+        Opcode::LessThan,
+        Opcode::Equals,
+        Opcode::AdjustRelativeBase,
+        Opcode::Halt,
+    ];
+
+    pub fn from_code(code: i64) -> Option<Self> {
+        let opcode = match code {
+            1 => Opcode::Add,
+            2 => Opcode::Mul,
+            3 => Opcode::Input,
+            4 => Opcode::Output,
+            5 => Opcode::JumpIfTrue,
+            6 => Opcode::JumpIfFalse,
+            7 => Opcode::LessThan,
+            8 => Opcode::Equals,
+            9 => Opcode::AdjustRelativeBase,
+            99 => Opcode::Halt,
+            _ => return None,
+        };
+        Some(opcode)
+    }
+
+    pub fn from_mnemonic(mnemonic: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|opcode| opcode.mnemonic() == mnemonic)
+    }
+
+    /// The numeric opcode, without any parameter modes.
+    pub fn code(self) -> i64 {
+        match self {
+            Opcode::Add => 1,
+            Opcode::Mul => 2,
+            Opcode::Input => 3,
+            Opcode::Output => 4,
+            Opcode::JumpIfTrue => 5,
+            Opcode::JumpIfFalse => 6,
+            Opcode::LessThan => 7,
+            Opcode::Equals => 8,
+            Opcode::AdjustRelativeBase => 9,
+            Opcode::Halt => 99,
+            Opcode::Custom(opcode) => opcode.code,
+        }
+    }
+
+    pub fn arity(self) -> usize {
+        match self {
+            Opcode::Add | Opcode::Mul | Opcode::LessThan | Opcode::Equals => 3,
+            Opcode::JumpIfTrue | Opcode::JumpIfFalse => 2,
+            Opcode::Input | Opcode::Output | Opcode::AdjustRelativeBase => 1,
+            Opcode::Halt => 0,
+            Opcode::Custom(opcode) => opcode.arity,
+        }
+    }
+
+    /// Index of the parameter this opcode writes to, if any.
+    pub fn destination(self) -> Option<usize> {
+        match self {
+            Opcode::Add | Opcode::Mul | Opcode::LessThan | Opcode::Equals => Some(2),
+            Opcode::Input => Some(0),
+            Opcode::Custom(opcode) => opcode.destination,
+            _ => None,
+        }
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Add => "add",
+            Opcode::Mul => "mul",
+            Opcode::Input => "in",
+            Opcode::Output => "out",
+            Opcode::JumpIfTrue => "jt",
+            Opcode::JumpIfFalse => "jf",
+            Opcode::LessThan => "lt",
+            Opcode::Equals => "eq",
+            Opcode::AdjustRelativeBase => "arb",
+            Opcode::Halt => "hlt",
+            Opcode::Custom(opcode) => opcode.mnemonic,
+        }
+    }
 }
 
-#[derive(Debug)]
-enum Instruction {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instruction {
     Add(Operand, Operand, Operand),
     Mul(Operand, Operand, Operand),
     Input(Operand),
@@ -283,6 +1002,191 @@ enum Instruction {
     Equals(Operand, Operand, Operand),
     AdjustRelativeBase(Operand),
     Halt,
+    /// Operands past the opcode's arity are unused.
+    Custom(CustomOpcode, [Operand; 3]),
+}
+
+impl Instruction {
+    /// Number of words in the largest instruction.
+    const MAX_SIZE: usize = 4;
+
+    fn decode_opcode(
+        i: i64,
+        custom: &[CustomOpcode],
+    ) -> Option<(Opcode, ParameterMode, ParameterMode, ParameterMode)> {
+        if !(0..100_000).contains(&i) {
+            return None;
+        }
+
+        let opcode = match Opcode::from_code(i % 100) {
+            Some(opcode) => opcode,
+            None => custom
+                .iter()
+                .find(|opcode| opcode.code == i % 100)
+                .map(|&opcode| Opcode::Custom(opcode))?,
+        };
+        let mode = |digit: i64| match digit % 10 {
+            0 => Some(ParameterMode::Position),
+            1 => Some(ParameterMode::Immediate),
+            2 => Some(ParameterMode::Relative),
+            _ => None,
+        };
+
+        Some((opcode, mode(i / 100)?, mode(i / 1000)?, mode(i / 10000)?))
+    }
+
+    /// Decodes the instruction at `ip`, reading its words through `read`.
+    /// Returns `None` if the instruction is invalid or `read` fails.
+    fn decode(ip: usize, read: impl Fn(usize) -> Option<i64>) -> Option<Self> {
+        Self::decode_with(ip, read, &[])
+    }
+
+    /// Like `decode`, but also recognizes the `custom` opcodes.
+    fn decode_with(
+        ip: usize,
+        read: impl Fn(usize) -> Option<i64>,
+        custom: &[CustomOpcode],
+    ) -> Option<Self> {
+        use ParameterMode::*;
+
+        let inst = read(ip)?;
+        let operand = |idx: usize, mode: ParameterMode| -> Option<Operand> {
+            let param = read(ip + idx)?;
+            let operand = match mode {
+                Position => Operand::Position(param),
+                Immediate => Operand::Immediate(param),
+                Relative => Operand::Relative(param),
+            };
+            Some(operand)
+        };
+
+        let instruction = match Self::decode_opcode(inst, custom)? {
+            (Opcode::Add, p1, p2, p3) => {
+                Instruction::Add(operand(1, p1)?, operand(2, p2)?, operand(3, p3)?)
+            }
+            (Opcode::Mul, p1, p2, p3) => {
+                Instruction::Mul(operand(1, p1)?, operand(2, p2)?, operand(3, p3)?)
+            }
+            (Opcode::Input, p1, Position, Position) => Instruction::Input(operand(1, p1)?),
+            (Opcode::Output, p1, Position, Position) => Instruction::Output(operand(1, p1)?),
+            (Opcode::JumpIfTrue, p1, p2, Position) => {
+                Instruction::JumpIfTrue(operand(1, p1)?, operand(2, p2)?)
+            }
+            (Opcode::JumpIfFalse, p1, p2, Position) => {
+                Instruction::JumpIfFalse(operand(1, p1)?, operand(2, p2)?)
+            }
+            (Opcode::LessThan, p1, p2, p3) => {
+                Instruction::LessThan(operand(1, p1)?, operand(2, p2)?, operand(3, p3)?)
+            }
+            (Opcode::Equals, p1, p2, p3) => {
+                Instruction::Equals(operand(1, p1)?, operand(2, p2)?, operand(3, p3)?)
+            }
+            (Opcode::AdjustRelativeBase, p1, Position, Position) => {
+                Instruction::AdjustRelativeBase(operand(1, p1)?)
+            }
+            (Opcode::Halt, Position, Position, Position) => Instruction::Halt,
+            (Opcode::Custom(opcode), p1, p2, p3) => {
+                // Unused parameters must be left in position mode
+                let mut operands = [Operand::Position(0); 3];
+                for (idx, &mode) in [p1, p2, p3].iter().enumerate() {
+                    match mode {
+                        _ if idx < opcode.arity => operands[idx] = operand(idx + 1, mode)?,
+                        Position => {}
+                        _ => return None,
+                    }
+                }
+                Instruction::Custom(opcode, operands)
+            }
+            (_, _, _, _) => return None,
+        };
+
+        Some(instruction)
+    }
+
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Instruction::Add(..) => Opcode::Add,
+            Instruction::Mul(..) => Opcode::Mul,
+            Instruction::Input(..) => Opcode::Input,
+            Instruction::Output(..) => Opcode::Output,
+            Instruction::JumpIfTrue(..) => Opcode::JumpIfTrue,
+            Instruction::JumpIfFalse(..) => Opcode::JumpIfFalse,
+            Instruction::LessThan(..) => Opcode::LessThan,
+            Instruction::Equals(..) => Opcode::Equals,
+            Instruction::AdjustRelativeBase(..) => Opcode::AdjustRelativeBase,
+            Instruction::Halt => Opcode::Halt,
+            Instruction::Custom(opcode, _) => Opcode::Custom(*opcode),
+        }
+    }
+
+    pub fn operands(&self) -> Operands {
+        use Instruction::*;
+
+        let unused = Operand::Position(0);
+        let (operands, len) = match *self {
+            Add(a, b, c) | Mul(a, b, c) | LessThan(a, b, c) | Equals(a, b, c) => ([a, b, c], 3),
+            JumpIfTrue(a, b) | JumpIfFalse(a, b) => ([a, b, unused], 2),
+            Input(a) | Output(a) | AdjustRelativeBase(a) => ([a, unused, unused], 1),
+            Halt => ([unused; 3], 0),
+            Custom(opcode, operands) => (operands, opcode.arity),
+        };
+        Operands { operands, len }
+    }
+
+    /// The operand this instruction writes to, if any.
+    pub fn destination(&self) -> Option<Operand> {
+        self.opcode().destination().map(|idx| self.operands()[idx])
+    }
+
+    /// The operands this instruction reads from.
+    pub fn sources(&self) -> Operands {
+        let mut operands = self.operands();
+        if let Some(idx) = self.opcode().destination() {
+            operands.operands.copy_within(idx + 1.., idx);
+            operands.len -= 1;
+        }
+        operands
+    }
+
+    /// Number of words this instruction occupies, including the opcode.
+    pub fn size(&self) -> usize {
+        1 + self.opcode().arity()
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.opcode().mnemonic())?;
+        for (idx, operand) in self.operands().iter().enumerate() {
+            let sep = if idx == 0 { " " } else { ", " };
+            write!(f, "{}{}", sep, operand)?;
+        }
+        Ok(())
+    }
+}
+
+/// An instruction's operands, held inline rather than on the heap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Operands {
+    operands: [Operand; 3],
+    len: usize,
+}
+
+impl Deref for Operands {
+    type Target = [Operand];
+
+    fn deref(&self) -> &[Operand] {
+        &self.operands[..self.len]
+    }
+}
+
+impl IntoIterator for Operands {
+    type Item = Operand;
+    type IntoIter = iter::Take<array::IntoIter<Operand, 3>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter(self.operands).take(self.len)
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -292,19 +1196,419 @@ enum ParameterMode {
     Relative,
 }
 
-#[derive(Debug)]
-enum Operand {
-    Position(usize),
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operand {
+    Position(i64),
     Immediate(i64),
     Relative(i64),
 }
 
-#[derive(Debug, Error)]
+impl Operand {
+    /// Parameter mode digit used when encoding this operand.
+    pub fn mode(&self) -> i64 {
+        match self {
+            Operand::Position(_) => 0,
+            Operand::Immediate(_) => 1,
+            Operand::Relative(_) => 2,
+        }
+    }
+
+    pub fn value(&self) -> i64 {
+        match *self {
+            Operand::Position(v) | Operand::Immediate(v) | Operand::Relative(v) => v,
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Operand::Position(addr) => write!(f, "[{}]", addr),
+            Operand::Immediate(val) => write!(f, "#{}", val),
+            Operand::Relative(offset) if offset < 0 => write!(f, "rb-{}", offset.unsigned_abs()),
+            Operand::Relative(offset) => write!(f, "rb+{}", offset),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Item {
+    Instruction(Instruction),
+    Data(i64),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Disassembled {
+    pub address: usize,
+    pub words: Vec<i64>,
+    pub item: Item,
+}
+
+impl fmt::Display for Disassembled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let words = self.words.iter().map(i64::to_string).collect::<Vec<_>>();
+        write!(f, "{:05}: {:<24} ", self.address, words.join(","))?;
+        match &self.item {
+            Item::Instruction(instruction) => write!(f, "{}", instruction),
+            Item::Data(value) => write!(f, ".data {}", value),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Disassembly {
+    items: Vec<Disassembled>,
+}
+
+impl Disassembly {
+    pub fn items(&self) -> &[Disassembled] {
+        &self.items
+    }
+
+    /// The item covering `address`, if any.
+    pub fn at(&self, address: usize) -> Option<&Disassembled> {
+        let idx = match self
+            .items
+            .binary_search_by_key(&address, |item| item.address)
+        {
+            Ok(idx) => idx,
+            Err(0) => return None,
+            Err(idx) => idx - 1,
+        };
+        let item = &self.items[idx];
+        if address < item.address + item.words.len() {
+            Some(item)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Disassembly {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for item in &self.items {
+            writeln!(f, "{}", item)?;
+        }
+        Ok(())
+    }
+}
+
+/// Linearly disassembles `program`, emitting `.data` for any word which
+/// doesn't decode to a complete instruction.
+pub fn disassemble(program: &[i64]) -> Disassembly {
+    disassemble_with(program, &[])
+}
+
+/// Like `disassemble`, but also recognizes the `custom` opcodes.
+pub fn disassemble_with(program: &[i64], custom: &[CustomOpcode]) -> Disassembly {
+    let read = |address: usize| program.get(address).copied();
+
+    let mut items = Vec::new();
+    let mut address = 0;
+    while address < program.len() {
+        let item = match Instruction::decode_with(address, read, custom) {
+            Some(instruction) => Disassembled {
+                address,
+                words: program[address..address + instruction.size()].to_vec(),
+                item: Item::Instruction(instruction),
+            },
+            None => Disassembled {
+                address,
+                words: vec![program[address]],
+                item: Item::Data(program[address]),
+            },
+        };
+        address += item.words.len();
+        items.push(item);
+    }
+
+    Disassembly { items }
+}
+
+/// The state of the machine at the instruction which faulted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FaultContext {
+    pub ip: usize,
+    /// The raw instruction word at `ip`.
+    pub word: i64,
+    /// The opcode encoded in `word`, if it names a known one.
+    pub opcode: Option<Opcode>,
+    pub relative_base: i64,
+}
+
+impl fmt::Display for FaultContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ip {} (instruction {}", self.ip, self.word)?;
+        if let Some(opcode) = self.opcode {
+            write!(f, ", opcode {}", opcode.mnemonic())?;
+        }
+        write!(f, ", relative base {})", self.relative_base)
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
 pub enum IntcodeError {
-    #[error("address out of bounds: {0}")]
-    AddressOutOfBound(usize),
-    #[error("invalid instruction '{0}'")]
-    InvalidInstruction(i64),
-    #[error("waiting for input")]
-    WaitingForInput,
+    #[error("invalid instruction at {context}")]
+    InvalidInstruction { context: FaultContext },
+    #[error("negative address {address} at {context}")]
+    NegativeAddress { address: i64, context: FaultContext },
+    #[error("write to immediate operand at {context}")]
+    WriteToImmediate { context: FaultContext },
+    #[error("arithmetic overflow at {context}")]
+    Overflow { context: FaultContext },
+    #[error("{message} at {context}")]
+    Handler {
+        message: String,
+        context: FaultContext,
+    },
+}
+
+impl IntcodeError {
+    pub fn context(&self) -> &FaultContext {
+        match self {
+            IntcodeError::InvalidInstruction { context }
+            | IntcodeError::NegativeAddress { context, .. }
+            | IntcodeError::WriteToImmediate { context }
+            | IntcodeError::Overflow { context }
+            | IntcodeError::Handler { context, .. } => context,
+        }
+    }
+}
+
+#[test]
+fn disassemble_quine() {
+    let program = [
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+    let listing = disassemble(&program)
+        .items()
+        .iter()
+        .map(|item| match &item.item {
+            Item::Instruction(instruction) => instruction.to_string(),
+            Item::Data(value) => format!(".data {}", value),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        listing,
+        [
+            "arb #1",
+            "out rb-1",
+            "add [100], #1, [100]",
+            "eq [100], #16, [101]",
+            "jf [101], #0",
+            "hlt",
+        ]
+    );
+}
+
+#[test]
+fn disassemble_data() {
+    let program = [1101, 2, 3, 4, 42, 7, 1];
+    let disassembly = disassemble(&program);
+    let items = disassembly.items();
+
+    assert_eq!(items.len(), 4);
+    assert_eq!(items[1].item, Item::Data(42));
+    assert_eq!(items[2].item, Item::Data(7));
+    // Truncated instruction at the end of the program
+    assert_eq!(items[3].item, Item::Data(1));
+    assert_eq!(disassembly.at(2).map(|item| item.address), Some(0));
+    assert_eq!(
+        items[0].to_string(),
+        "00000: 1101,2,3,4               add #2, #3, [4]"
+    );
+}
+
+#[test]
+fn step_and_breakpoints() {
+    let program = crate::assemble(
+        "
+              in [sum]
+        loop: add [sum], [sum], [sum]
+              add [count], #-1, [count]
+              jt [count], #loop
+              out [sum]
+              hlt
+        sum:   data 0
+        count: data 3
+        ",
+    )
+    .unwrap();
+
+    let mut computer = IntcodeComputer::new(&program);
+    computer.input(1);
+    assert_eq!(
+        computer.step().unwrap(),
+        Some(Instruction::Input(Operand::Position(16)))
+    );
+    assert_eq!(computer.ip(), 2);
+    assert_eq!(computer.memory().read(16), 1);
+
+    computer.set_breakpoint(2);
+    assert_eq!(
+        computer.run_until(|_| false).unwrap(),
+        RunState::Breakpoint(2)
+    );
+    assert_eq!(computer.memory().read(16), 1);
+    assert_eq!(
+        computer.run_until(|_| false).unwrap(),
+        RunState::Breakpoint(2)
+    );
+    assert_eq!(computer.memory().read(16), 2);
+    assert_eq!(
+        computer.run_until(|_| false).unwrap(),
+        RunState::Breakpoint(2)
+    );
+    assert_eq!(computer.memory().read(16), 4);
+
+    assert!(computer.clear_breakpoint(2));
+    assert_eq!(
+        computer.run_until(|c| c.ip() == 13).unwrap(),
+        RunState::Paused
+    );
+    assert!(computer.output().is_empty());
+    assert_eq!(computer.run_until(|_| false).unwrap(), RunState::Halted);
+    assert_eq!(computer.output(), [8]);
+    assert_eq!(computer.step().unwrap(), None);
+}
+
+#[test]
+fn run_states() {
+    let program = crate::assemble(
+        "
+        loop: in [100]
+              out [100]
+              out #7
+              jt [100], #loop
+              hlt
+        ",
+    )
+    .unwrap();
+
+    let mut computer = IntcodeComputer::new(&program);
+    assert_eq!(computer.status(), RunState::Paused);
+    assert_eq!(computer.run().unwrap(), RunState::NeedsInput);
+    assert_eq!(computer.step().unwrap(), None);
+    assert_eq!(computer.status(), RunState::NeedsInput);
+
+    computer.input(3);
+    assert_eq!(computer.run_to_output().unwrap(), RunState::Output(3));
+    assert_eq!(computer.run_steps(1).unwrap(), RunState::StepLimit);
+    assert_eq!(computer.output(), [3, 7]);
+    assert_eq!(computer.run().unwrap(), RunState::NeedsInput);
+
+    computer.set_breakpoint(2);
+    computer.input(0);
+    assert_eq!(computer.run().unwrap(), RunState::Breakpoint(2));
+    assert_eq!(computer.status(), RunState::Breakpoint(2));
+    assert_eq!(computer.run().unwrap(), RunState::Halted);
+    assert_eq!(computer.output(), [3, 7, 0, 7]);
+    assert_eq!(computer.run().unwrap(), RunState::Halted);
+}
+
+#[test]
+fn faults() {
+    let run = |program: &[i64]| IntcodeComputer::new(program).run().unwrap_err();
+
+    // A fault stops the machine at the faulting instruction
+    let mut computer = IntcodeComputer::new(&[104, 1, 1105, 1, -1]);
+    assert!(computer.run().is_err());
+    assert_eq!((computer.status(), computer.ip()), (RunState::Faulted, 2));
+    assert_eq!(computer.run(), Ok(RunState::Faulted));
+    assert_eq!(computer.step(), Ok(None));
+    assert_eq!(computer.output(), [1]);
+
+    assert_eq!(
+        run(&[109, 3, 1101, 1, 2, 3, 301, 0, 0, 0]),
+        IntcodeError::InvalidInstruction {
+            context: FaultContext {
+                ip: 6,
+                word: 301,
+                opcode: Some(Opcode::Add),
+                relative_base: 3,
+            }
+        }
+    );
+    assert_eq!(
+        run(&[109, 3, 204, -4, 99]),
+        IntcodeError::NegativeAddress {
+            address: -1,
+            context: FaultContext {
+                ip: 2,
+                word: 204,
+                opcode: Some(Opcode::Output),
+                relative_base: 3,
+            }
+        }
+    );
+    assert_eq!(
+        *run(&[1105, 1, -5]).context(),
+        FaultContext {
+            ip: 0,
+            word: 1105,
+            opcode: Some(Opcode::JumpIfTrue),
+            relative_base: 0,
+        }
+    );
+    assert_eq!(
+        run(&[109, i64::MAX, 204, 1, 99]),
+        IntcodeError::Overflow {
+            context: FaultContext {
+                ip: 2,
+                word: 204,
+                opcode: Some(Opcode::Output),
+                relative_base: i64::MAX,
+            }
+        }
+    );
+    assert_eq!(
+        run(&[11101, 1, 2, 3]).to_string(),
+        "write to immediate operand at ip 0 (instruction 11101, opcode add, relative base 0)"
+    );
+}
+
+#[test]
+fn high_addresses() {
+    let program = crate::assemble(
+        "
+        in [100000]
+        add [100000], #1, rb+200000
+        arb #200000
+        out rb+0
+        hlt
+        ",
+    )
+    .unwrap();
+
+    for mut computer in [
+        IntcodeComputer::new(&program),
+        IntcodeComputer::with_memory(&program, PagedMemory::default()),
+    ] {
+        computer.input(41);
+        computer.run().unwrap();
+        assert_eq!(computer.output(), [42]);
+        assert_eq!(computer.memory().read(100_000), 41);
+    }
+
+    // Far beyond anything which could be allocated contiguously
+    let program = [
+        1101,
+        1,
+        1,
+        1_000_000_000_000_000,
+        4,
+        1_000_000_000_000_000,
+        99,
+    ];
+    for mut computer in [
+        IntcodeComputer::new(&program),
+        IntcodeComputer::with_memory(&program, PagedMemory::default()),
+        IntcodeComputer::shared(program[..].into()),
+    ] {
+        assert_eq!(computer.run().unwrap(), RunState::Halted);
+        assert_eq!(computer.output(), [2]);
+        assert_eq!(computer.memory().read(1_000_000_000_000_000), 2);
+        assert_eq!(computer.snapshot().memory.len(), 2);
+    }
 }
@@ -0,0 +1,479 @@
+use super::cfg::{ends_block, ControlFlowGraph};
+use super::limits::Limits;
+use super::{Instruction, IntcodeComputer, Operand, Result, RunState};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+/// Translates `program` into the source of a Rust module exposing
+/// `pub fn run(computer: &mut IntcodeComputer) -> Result<RunState, IntcodeError>`.
+///
+/// Instructions reachable from address zero, or from where a linear
+/// disassembly suggests code starts, are compiled into a state machine over
+/// basic blocks. Anything else is left to the interpreter until execution
+/// reaches a translated block again, including blocks which the program has
+/// overwritten and any instruction which would fault.
+///
+/// The generated `run` behaves like `IntcodeComputer::run`, including its
+/// budget and timeout, except that breakpoints, watchpoints, traces, history
+/// and profiles aren't updated while translated code runs.
+pub fn translate(program: &[i64]) -> String {
+    let cfg = ControlFlowGraph::new(program);
+    let instructions = cfg
+        .blocks()
+        .flat_map(|block| block.instructions.iter().copied())
+        .collect::<BTreeMap<_, _>>();
+
+    // Merge the words of every translated instruction into contiguous segments
+    let mut code: Vec<(usize, usize)> = Vec::new();
+    for (&address, instruction) in &instructions {
+        let end = address + instruction.size();
+        match code.last_mut() {
+            Some((_, last_end)) if address <= *last_end => *last_end = end.max(*last_end),
+            _ => code.push((address, end)),
+        }
+    }
+    let in_code = |address: usize| {
+        code.iter()
+            .any(|&(start, end)| (start..end).contains(&address))
+    };
+
+    // Input instructions start blocks of their own, so that execution can
+    // resume there after blocking
+    let mut arms = Vec::new();
+    for block in cfg.blocks() {
+        let mut rest = &block.instructions[..];
+        while !rest.is_empty() {
+            let len = rest[1..]
+                .iter()
+                .position(|(_, instruction)| matches!(instruction, Instruction::Input(_)))
+                .map_or(rest.len(), |idx| idx + 1);
+            let (arm, tail) = rest.split_at(len);
+            arms.push(arm);
+            rest = tail;
+        }
+    }
+
+    let mut blocks = Vec::new();
+    let mut bodies = String::new();
+    for arm in arms {
+        let leader = arm[0].0;
+        writeln!(bodies, "            Some({}) => {{", leader).unwrap();
+        let mut end = leader;
+        for (idx, &(address, instruction)) in arm.iter().enumerate() {
+            let next = address + instruction.size();
+            end = next;
+            let mut block = Block {
+                out: &mut bodies,
+                address,
+                next,
+            };
+            if !block.emit(instruction, &in_code) {
+                break;
+            }
+            if idx == arm.len() - 1 {
+                writeln!(bodies, "                m.set_ip({});", next).unwrap();
+            }
+        }
+        bodies.push_str("            }\n");
+        blocks.push((leader, end, arm.len()));
+    }
+
+    let mut out = String::new();
+    out.push_str(HEADER);
+    out.push_str("static CODE: &[(usize, &[i64])] = &[\n");
+    for &(start, end) in &code {
+        writeln!(out, "    ({}, &[", start).unwrap();
+        for words in program[start..end].chunks(16) {
+            let words = words.iter().map(i64::to_string).collect::<Vec<_>>();
+            writeln!(out, "        {},", words.join(", ")).unwrap();
+        }
+        out.push_str("    ]),\n");
+    }
+    out.push_str("];\n\n");
+
+    out.push_str("static BLOCKS: &[(usize, usize, usize)] = &[\n");
+    for chunk in blocks.chunks(8) {
+        let chunk = chunk
+            .iter()
+            .map(|(start, end, len)| format!("({}, {}, {})", start, end, len))
+            .collect::<Vec<_>>();
+        writeln!(out, "    {},", chunk.join(", ")).unwrap();
+    }
+    out.push_str("];\n");
+
+    out.push_str(
+        "
+pub fn run(computer: &mut IntcodeComputer) -> Result<RunState, IntcodeError> {
+    let mut m = AotMachine::new(computer, CODE, BLOCKS);
+    let result = execute(&mut m);
+    m.finish(result)
+}
+
+fn execute(m: &mut AotMachine) -> Result<RunState, IntcodeError> {
+    loop {
+        match m.block() {
+",
+    );
+    out.push_str(&bodies);
+    out.push_str(
+        "            _ => {
+                if let Some(state) = m.interpret()? {
+                    return Ok(state);
+                }
+            }
+        }
+    }
+}
+",
+    );
+    out
+}
+
+const HEADER: &str = "\
+// Generated by intcode-aot, do not edit.
+#![allow(clippy::all, unused_parens)]
+
+use advent_of_code_2019::{AotMachine, IntcodeComputer, IntcodeError, RunState};
+
+";
+
+fn literal(value: i64) -> String {
+    if value == i64::MIN {
+        "i64::MIN".to_owned()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Emits the code for instructions within a single basic block.
+struct Block<'a> {
+    out: &'a mut String,
+    address: usize,
+    next: usize,
+}
+
+impl Block<'_> {
+    fn line(&mut self, line: &str) {
+        self.out.push_str("                ");
+        self.out.push_str(line);
+        self.out.push('\n');
+    }
+
+    /// An expression reading `operand`, or `None` if reading it always faults.
+    fn load(operand: Operand) -> Option<String> {
+        match operand {
+            Operand::Position(address) if address >= 0 => Some(format!("m.load({})", address)),
+            Operand::Position(_) => None,
+            Operand::Immediate(value) => Some(literal(value)),
+            Operand::Relative(offset) => Some(format!("m.load_relative({})?", literal(offset))),
+        }
+    }
+
+    /// Emits `instruction`, returning false if control never falls through
+    /// to the next instruction.
+    fn emit(&mut self, instruction: Instruction, in_code: &impl Fn(usize) -> bool) -> bool {
+        use Instruction::*;
+
+        let (address, next) = (self.address, self.next);
+        let sources = instruction
+            .sources()
+            .into_iter()
+            .map(Self::load)
+            .collect::<Option<Vec<_>>>();
+        let writable = match instruction.destination() {
+            Some(Operand::Position(address)) => address >= 0,
+            Some(Operand::Immediate(_)) => false,
+            _ => true,
+        };
+        let sources = match sources {
+            Some(sources) if writable => sources,
+            _ => {
+                // Let the interpreter report the fault
+                self.line(&format!("m.set_ip({});", address));
+                self.line("if let Some(state) = m.interpret()? {");
+                self.line("    return Ok(state);");
+                self.line("}");
+                self.line("continue;");
+                return false;
+            }
+        };
+
+        self.line(&format!("m.set_ip({});", address));
+        self.line("m.retire();");
+        let value = match instruction {
+            Add(..) => format!("m.add({}, {})?", sources[0], sources[1]),
+            Mul(..) => format!("m.mul({}, {})?", sources[0], sources[1]),
+            LessThan(..) => format!("({} < {}) as i64", sources[0], sources[1]),
+            Equals(..) => format!("({} == {}) as i64", sources[0], sources[1]),
+            Input(_) => {
+                "match m.input() { Some(v) => v, None => return Ok(m.needs_input()) }".to_owned()
+            }
+            Output(_) => {
+                self.line(&format!("m.output({});", sources[0]));
+                return true;
+            }
+            JumpIfTrue(cond, target) | JumpIfFalse(cond, target) => {
+                let jump = match target {
+                    Operand::Immediate(target) if target >= 0 => format!("m.set_ip({});", target),
+                    _ => format!("m.jump({})?;", sources[1]),
+                };
+                if ends_block(instruction) {
+                    self.line(&jump);
+                    return false;
+                }
+                if let Operand::Immediate(_) = cond {
+                    // Never taken
+                    return true;
+                }
+                let test = if let JumpIfTrue(..) = instruction {
+                    "!="
+                } else {
+                    "=="
+                };
+                self.line(&format!("if {} {} 0 {{", sources[0], test));
+                self.line(&format!("    {}", jump));
+                self.line("    continue;");
+                self.line("}");
+                return true;
+            }
+            AdjustRelativeBase(_) => {
+                self.line(&format!("m.adjust_relative_base({})?;", sources[0]));
+                return true;
+            }
+            Halt => {
+                self.line("return Ok(m.halt());");
+                return false;
+            }
+            Custom(..) => unreachable!("custom opcodes aren't decoded statically"),
+        };
+
+        // Writes to translated code leave the block, as it may now be stale
+        self.line(&format!("let v = {};", value));
+        match instruction.destination() {
+            Some(Operand::Position(target)) if in_code(target as usize) => {
+                self.line(&format!("m.patch({}, v);", target));
+                self.line(&format!("m.set_ip({});", next));
+                self.line("continue;");
+                return false;
+            }
+            Some(Operand::Position(target)) => self.line(&format!("m.store({}, v);", target)),
+            Some(Operand::Relative(offset)) => {
+                self.line(&format!("if m.store_relative({}, v)? {{", literal(offset)));
+                self.line(&format!("    m.set_ip({});", next));
+                self.line("    continue;");
+                self.line("}");
+            }
+            _ => unreachable!(),
+        }
+        true
+    }
+}
+
+/// Runtime used by code generated by `translate`, giving it access to the
+/// memory, registers and I/O of an `IntcodeComputer`.
+pub struct AotMachine<'a> {
+    computer: &'a mut IntcodeComputer,
+    /// The translated instructions' words, by start address.
+    code: &'static [(usize, &'static [i64])],
+    /// Address ranges and instruction counts of every translated block,
+    /// sorted by start address.
+    blocks: &'static [(usize, usize, usize)],
+    /// Blocks which have been overwritten since being translated.
+    stale: BTreeSet<usize>,
+    limits: Limits,
+}
+
+impl<'a> AotMachine<'a> {
+    pub fn new(
+        computer: &'a mut IntcodeComputer,
+        code: &'static [(usize, &'static [i64])],
+        blocks: &'static [(usize, usize, usize)],
+    ) -> Self {
+        let limits = Limits::start(computer.budget, computer.timeout);
+        let mut machine = Self {
+            computer,
+            code,
+            blocks,
+            stale: BTreeSet::new(),
+            limits,
+        };
+        for &(start, words) in code {
+            let memory = machine.computer.memory.read_range(start, words.len());
+            for (idx, (&a, &b)) in memory.iter().zip(words).enumerate() {
+                if a != b {
+                    machine.invalidate(start + idx);
+                }
+            }
+        }
+        machine
+    }
+
+    fn in_code(&self, address: usize) -> bool {
+        match self
+            .code
+            .binary_search_by_key(&address, |&(start, _)| start)
+        {
+            Ok(_) => true,
+            Err(0) => false,
+            Err(idx) => {
+                let (start, words) = self.code[idx - 1];
+                address < start + words.len()
+            }
+        }
+    }
+
+    fn invalidate(&mut self, address: usize) {
+        let blocks = self
+            .blocks
+            .iter()
+            .take_while(|&&(start, _, _)| start <= address);
+        for &(start, end, _) in blocks {
+            if address < end {
+                self.stale.insert(start);
+            }
+        }
+    }
+
+    /// The translated block to run next, or `None` if the interpreter needs
+    /// to take over.
+    ///
+    /// Blocks which could run past the budget, along with checking the
+    /// limits themselves, are left to the interpreter.
+    pub fn block(&mut self) -> Option<usize> {
+        let ip = self.computer.ip;
+        if self.computer.stopped() || self.stale.contains(&ip) {
+            return None;
+        }
+        let idx = self
+            .blocks
+            .binary_search_by_key(&ip, |&(start, _, _)| start)
+            .ok()?;
+        if self.limits.exceeded().is_some() || !self.limits.covers(self.blocks[idx].2 as u64) {
+            return None;
+        }
+        Some(ip)
+    }
+
+    /// Counts an instruction of a translated block against the budget.
+    pub fn retire(&mut self) {
+        self.limits.retire();
+    }
+
+    pub fn set_ip(&mut self, ip: usize) {
+        self.computer.ip = ip;
+    }
+
+    pub fn load(&self, address: usize) -> i64 {
+        self.computer.memread(address)
+    }
+
+    pub fn load_relative(&self, offset: i64) -> Result<i64> {
+        self.computer.read_operand(Operand::Relative(offset))
+    }
+
+    /// Writes to an address which isn't part of any translated instruction.
+    pub fn store(&mut self, address: usize, value: i64) {
+        self.computer.memwrite(address, value)
+    }
+
+    /// Writes to an address which is part of a translated instruction.
+    pub fn patch(&mut self, address: usize, value: i64) {
+        self.computer.memwrite(address, value);
+        self.invalidate(address);
+    }
+
+    /// Returns true if the write modified translated code.
+    pub fn store_relative(&mut self, offset: i64, value: i64) -> Result<bool> {
+        let address = self.computer.operand_address(Operand::Relative(offset))?;
+        let address = address.expect("relative operands have an address");
+        self.computer.memwrite(address, value);
+        if !self.in_code(address) {
+            return Ok(false);
+        }
+        self.invalidate(address);
+        Ok(true)
+    }
+
+    pub fn add(&self, a: i64, b: i64) -> Result<i64> {
+        let computer = &*self.computer;
+        computer
+            .arithmetic
+            .add(a, b)
+            .ok_or_else(|| computer.overflow())
+    }
+
+    pub fn mul(&self, a: i64, b: i64) -> Result<i64> {
+        let computer = &*self.computer;
+        computer
+            .arithmetic
+            .mul(a, b)
+            .ok_or_else(|| computer.overflow())
+    }
+
+    pub fn adjust_relative_base(&mut self, delta: i64) -> Result<()> {
+        self.computer.adjust_relative_base(delta)
+    }
+
+    pub fn jump(&mut self, target: i64) -> Result<()> {
+        self.computer.ip = self.computer.address(target)?;
+        Ok(())
+    }
+
+    pub fn input(&mut self) -> Option<i64> {
+        self.computer.next_input()
+    }
+
+    pub fn output(&mut self, value: i64) {
+        self.computer.emit(value)
+    }
+
+    pub fn needs_input(&mut self) -> RunState {
+        self.computer.state = RunState::NeedsInput;
+        RunState::NeedsInput
+    }
+
+    pub fn halt(&mut self) -> RunState {
+        self.computer.ip += 1;
+        self.computer.state = RunState::Halted;
+        RunState::Halted
+    }
+
+    /// Records the outcome of running translated code, which faults without
+    /// going through `IntcodeComputer::step`.
+    pub fn finish(&mut self, result: Result<RunState>) -> Result<RunState> {
+        self.computer.record_fault(result)
+    }
+
+    /// Interprets from the current ip until reaching a translated block,
+    /// returning the state instead if the program stops first.
+    pub fn interpret(&mut self) -> Result<Option<RunState>> {
+        loop {
+            match self.limits.exceeded() {
+                Some(state) if !self.computer.stopped() => {
+                    self.computer.state = state;
+                    return Ok(Some(state));
+                }
+                _ => {}
+            }
+            let computer = &*self.computer;
+            let destination = Instruction::decode(computer.ip, |a| Some(computer.memread(a)))
+                .and_then(|instruction| instruction.destination())
+                .and_then(|operand| computer.operand_address(operand).ok().flatten());
+            if let Some(address) = destination.filter(|&a| self.in_code(a)) {
+                self.invalidate(address);
+            }
+
+            if self.computer.step()?.is_none() || self.computer.state == RunState::Halted {
+                return Ok(Some(self.computer.state));
+            }
+            self.limits.retire();
+            let ip = self.computer.ip;
+            let translated = self
+                .blocks
+                .binary_search_by_key(&ip, |&(start, _, _)| start);
+            if translated.is_ok() && !self.stale.contains(&ip) {
+                return Ok(None);
+            }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::convert::TryFrom;
+
+/// How additions and multiplications, including adjustments to the relative
+/// base, behave when their result doesn't fit in an `i64`.
+///
+/// Relative addresses which overflow always fault, as a wrapped address
+/// would be meaningless.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Arithmetic {
+    /// Wrap around in two's complement, as release builds of plain `i64`
+    /// arithmetic would.
+    #[default]
+    Wrapping,
+    /// Fault with `IntcodeError::Overflow`.
+    Checked,
+    /// Work out the exact result as an `i128`, faulting with
+    /// `IntcodeError::Overflow` only when it can't be stored back in an `i64`.
+    Widened,
+}
+
+impl Arithmetic {
+    /// `None` if the addition overflows under a checked policy.
+    pub(super) fn add(self, a: i64, b: i64) -> Option<i64> {
+        match self {
+            Arithmetic::Wrapping => Some(a.wrapping_add(b)),
+            Arithmetic::Checked => a.checked_add(b),
+            Arithmetic::Widened => narrow(i128::from(a) + i128::from(b)),
+        }
+    }
+
+    /// `None` if the multiplication overflows under a checked policy.
+    pub(super) fn mul(self, a: i64, b: i64) -> Option<i64> {
+        match self {
+            Arithmetic::Wrapping => Some(a.wrapping_mul(b)),
+            Arithmetic::Checked => a.checked_mul(b),
+            Arithmetic::Widened => narrow(i128::from(a) * i128::from(b)),
+        }
+    }
+}
+
+fn narrow(value: i128) -> Option<i64> {
+    i64::try_from(value).ok()
+}
+
+#[test]
+fn overflow() {
+    use crate::{IntcodeComputer, IntcodeError, RunState};
+
+    // Day 9's large number examples are well within range
+    let large = [104, 1125899906842624, 99];
+    let square = [1102, 34915192, 34915192, 7, 4, 7, 99, 0];
+    for &arithmetic in &[Arithmetic::Checked, Arithmetic::Widened] {
+        for (program, expected) in [
+            (&large[..], 1125899906842624),
+            (&square[..], 1219070632396864),
+        ]
+        .iter()
+        {
+            let mut computer = IntcodeComputer::new(program);
+            computer.set_arithmetic(arithmetic);
+            assert_eq!(computer.run(), Ok(RunState::Halted));
+            assert_eq!(computer.output(), [*expected]);
+        }
+    }
+
+    // Squares its input until it no longer fits
+    let program = crate::assemble(
+        "
+              in [x]
+        loop: mul [x], [x], [x]
+              jt #1, #loop
+        x:    data 0
+        ",
+    )
+    .unwrap();
+
+    let mut computer = IntcodeComputer::new(&program);
+    assert_eq!(computer.arithmetic(), Arithmetic::Wrapping);
+    computer.input(3);
+    computer.run_steps(12).unwrap();
+    assert_eq!(
+        computer.memory().read(9),
+        3i64.pow(32).wrapping_mul(3i64.pow(32))
+    );
+
+    for &arithmetic in &[Arithmetic::Checked, Arithmetic::Widened] {
+        computer.reset(&program);
+        computer.set_arithmetic(arithmetic);
+        computer.input(3);
+        match computer.run() {
+            Err(IntcodeError::Overflow { context }) => assert_eq!(context.ip, 2),
+            result => panic!("unexpected result {:?}", result),
+        }
+        assert_eq!(computer.memory().read(9), 3i64.pow(32));
+
+        // Adjustments to the relative base are checked too
+        let mut computer = IntcodeComputer::new(&[109, i64::MAX, 109, 1, 99]);
+        computer.set_arithmetic(arithmetic);
+        assert_eq!(computer.run().unwrap_err().context().ip, 2);
+    }
+
+    // Results right at the edge of the range still fit
+    let program = [1002, 7, -1, 7, 4, 7, 99, i64::MAX];
+    let mut computer = IntcodeComputer::new(&program);
+    computer.set_arithmetic(Arithmetic::Widened);
+    assert_eq!(computer.run(), Ok(RunState::Halted));
+    assert_eq!(computer.output(), [-i64::MAX]);
+
+    for &arithmetic in &[
+        Arithmetic::Wrapping,
+        Arithmetic::Checked,
+        Arithmetic::Widened,
+    ] {
+        let mut computer = IntcodeComputer::new(&[109, i64::MAX, 204, 1, 99]);
+        computer.set_arithmetic(arithmetic);
+        assert!(matches!(computer.run(), Err(IntcodeError::Overflow { .. })));
+    }
+}
@@ -0,0 +1,132 @@
+use super::{IntcodeComputer, Pipe, Result, RunState};
+use std::iter;
+
+/// A run of output from an ASCII program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AsciiOutput {
+    Text(String),
+    /// A value outside the ASCII range, often a puzzle's answer.
+    Value(i64),
+}
+
+/// Splits `values` into runs of ASCII text and the values between them.
+pub fn decode_ascii(values: &[i64]) -> Vec<AsciiOutput> {
+    let mut decoded = Vec::new();
+    for &value in values {
+        match (decoded.last_mut(), value) {
+            (Some(AsciiOutput::Text(text)), 0..=127) => text.push(value as u8 as char),
+            (_, 0..=127) => decoded.push(AsciiOutput::Text((value as u8 as char).to_string())),
+            _ => decoded.push(AsciiOutput::Value(value)),
+        }
+    }
+    decoded
+}
+
+/// Drives an `IntcodeComputer` speaking ASCII, sending lines of text as input
+/// and decoding its output.
+///
+/// The console attaches its own output sink to the computer, so output is
+/// no longer buffered by the computer itself while the console owns it.
+pub struct AsciiConsole {
+    computer: IntcodeComputer,
+    /// Output not yet returned by `take_output`.
+    output: Pipe,
+}
+
+impl AsciiConsole {
+    pub fn new(mut computer: IntcodeComputer) -> Self {
+        let output = Pipe::new();
+        computer.attach_output(output.clone());
+        Self { computer, output }
+    }
+
+    /// Queues the characters of `line` followed by a newline, each as its
+    /// code point.
+    pub fn send_line(&mut self, line: &str) {
+        for c in line.chars().chain(Some('\n')) {
+            self.computer.input(c as i64);
+        }
+    }
+
+    pub fn run(&mut self) -> Result<RunState> {
+        self.computer.run()
+    }
+
+    /// Decodes everything output since the last call.
+    pub fn take_output(&mut self) -> Vec<AsciiOutput> {
+        let output = iter::from_fn(|| self.output.pop()).collect::<Vec<_>>();
+        decode_ascii(&output)
+    }
+
+    pub fn computer(&self) -> &IntcodeComputer {
+        &self.computer
+    }
+
+    pub fn computer_mut(&mut self) -> &mut IntcodeComputer {
+        &mut self.computer
+    }
+
+    /// Returns the computer with the console's output sink detached.
+    pub fn into_inner(mut self) -> IntcodeComputer {
+        self.computer.detach_output();
+        self.computer
+    }
+}
+
+#[test]
+fn echo_console() {
+    // Prompts for a line, echoes it and then outputs a non-ASCII value
+    let program = crate::assemble(
+        "
+              out #63
+              out #10
+        loop: in [c]
+              out [c]
+              eq [c], #10, [flag]
+              jf [flag], #loop
+              out #1000
+              hlt
+        c:    data 0
+        flag: data 0
+        ",
+    )
+    .unwrap();
+
+    let mut console = AsciiConsole::new(IntcodeComputer::new(&program));
+    assert_eq!(console.run().unwrap(), RunState::NeedsInput);
+    assert_eq!(console.take_output(), [AsciiOutput::Text("?\n".to_owned())]);
+    assert!(console.take_output().is_empty());
+
+    console.send_line("hi");
+    assert_eq!(console.run().unwrap(), RunState::Halted);
+    assert_eq!(
+        console.take_output(),
+        [
+            AsciiOutput::Text("hi\n".to_owned()),
+            AsciiOutput::Value(1000)
+        ]
+    );
+
+    // Output is taken in full after a reset, however much was taken before
+    console.computer_mut().reset(&program);
+    console.send_line("hello");
+    assert_eq!(console.run().unwrap(), RunState::Halted);
+    assert_eq!(
+        console.take_output(),
+        [
+            AsciiOutput::Text("?\nhello\n".to_owned()),
+            AsciiOutput::Value(1000)
+        ]
+    );
+    assert!(console.into_inner().output().is_empty());
+
+    assert_eq!(
+        decode_ascii(&[-1, 65, 66, 128, 67]),
+        [
+            AsciiOutput::Value(-1),
+            AsciiOutput::Text("AB".to_owned()),
+            AsciiOutput::Value(128),
+            AsciiOutput::Text("C".to_owned()),
+        ]
+    );
+}
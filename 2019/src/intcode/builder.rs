@@ -0,0 +1,62 @@
+use super::custom::{CustomOpcode, OpcodeTable};
+use super::{Arithmetic, DenseMemory, Engine, IntcodeComputer, Memory};
+use std::sync::Arc;
+
+/// Configures an `IntcodeComputer` before it's created, including any
+/// custom opcodes it should understand.
+pub struct Builder {
+    memory: Box<dyn Memory>,
+    engine: Engine,
+    arithmetic: Arithmetic,
+    opcodes: OpcodeTable,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            memory: Box::new(DenseMemory::default()),
+            engine: Engine::Interpreter,
+            arithmetic: Arithmetic::default(),
+            opcodes: OpcodeTable::default(),
+        }
+    }
+}
+
+impl Builder {
+    pub fn memory(mut self, memory: impl Memory + 'static) -> Self {
+        self.memory = Box::new(memory);
+        self
+    }
+
+    pub fn engine(mut self, engine: Engine) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    pub fn arithmetic(mut self, arithmetic: Arithmetic) -> Self {
+        self.arithmetic = arithmetic;
+        self
+    }
+
+    /// Adds an opcode, executed by calling `handler` with the values of its
+    /// source parameters and writing the result to its destination.
+    ///
+    /// Panics if the opcode clashes with a built-in or already added one, or
+    /// if its parameters aren't valid.
+    pub fn opcode(
+        mut self,
+        opcode: CustomOpcode,
+        handler: impl Fn(&[i64]) -> Result<i64, String> + Send + Sync + 'static,
+    ) -> Self {
+        self.opcodes.register(opcode, Arc::new(handler));
+        self
+    }
+
+    pub fn build(self, program: &[i64]) -> IntcodeComputer {
+        let mut computer = IntcodeComputer::with_boxed_memory(program, self.memory);
+        computer.set_engine(self.engine);
+        computer.set_arithmetic(self.arithmetic);
+        computer.opcodes = self.opcodes;
+        computer
+    }
+}
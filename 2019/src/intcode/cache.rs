@@ -0,0 +1,360 @@
+use super::{Arithmetic, CustomOpcode, Instruction, Limits, Operand, OutputSink, RunState};
+use std::collections::VecDeque;
+
+/// Instructions starting at addresses beyond this aren't cached.
+const MAX_CACHED_ADDRESS: usize = 1 << 20;
+
+/// Strategy used to fetch and decode instructions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Engine {
+    /// Decode every instruction from memory each time it executes.
+    Interpreter,
+    /// Keep decoded instructions per address, discarding them when the words
+    /// they were decoded from are written. This only pays off with memory
+    /// which stores cells contiguously, such as `DenseMemory`.
+    Cached,
+}
+
+/// An operand with everything that doesn't depend on the machine state
+/// worked out when it's decoded.
+#[derive(Clone, Copy, Debug)]
+pub(super) enum Arg {
+    Value(i64),
+    Address(usize),
+    Relative(i64),
+}
+
+impl Arg {
+    fn new(operand: Operand) -> Option<Self> {
+        match operand {
+            Operand::Position(address) if address >= 0 => Some(Arg::Address(address as usize)),
+            Operand::Position(_) => None,
+            Operand::Immediate(value) => Some(Arg::Value(value)),
+            Operand::Relative(offset) => Some(Arg::Relative(offset)),
+        }
+    }
+
+    fn destination(operand: Operand) -> Option<Self> {
+        match operand {
+            Operand::Immediate(_) => None,
+            operand => Self::new(operand),
+        }
+    }
+
+    /// The address this operand refers to, if it's stored in `cells`.
+    fn address(self, cells: &[i64], relative_base: i64) -> Option<usize> {
+        let address = match self {
+            Arg::Value(_) => return None,
+            Arg::Address(address) => address,
+            Arg::Relative(offset) => {
+                let address = relative_base.checked_add(offset)?;
+                if address < 0 {
+                    return None;
+                }
+                address as usize
+            }
+        };
+        if address < cells.len() {
+            Some(address)
+        } else {
+            None
+        }
+    }
+
+    fn load(self, cells: &[i64], relative_base: i64) -> Option<i64> {
+        match self {
+            Arg::Value(value) => Some(value),
+            _ => self.address(cells, relative_base).map(|a| cells[a]),
+        }
+    }
+}
+
+/// A decoded instruction in the form run by `FastLoop`.
+#[derive(Clone, Copy, Debug)]
+pub(super) enum Op {
+    Add(Arg, Arg, Arg),
+    Mul(Arg, Arg, Arg),
+    Input(Arg),
+    Output(Arg),
+    JumpIfTrue(Arg, Arg),
+    JumpIfFalse(Arg, Arg),
+    LessThan(Arg, Arg, Arg),
+    Equals(Arg, Arg, Arg),
+    AdjustRelativeBase(Arg),
+    /// Left to the interpreter: halts, custom opcodes and instructions which
+    /// always fault.
+    Interpret,
+}
+
+impl Op {
+    pub fn new(instruction: Instruction) -> Self {
+        use Instruction::*;
+
+        let (arg, dest) = (Arg::new, Arg::destination);
+        let op = || {
+            let op = match instruction {
+                Add(a, b, c) => Op::Add(arg(a)?, arg(b)?, dest(c)?),
+                Mul(a, b, c) => Op::Mul(arg(a)?, arg(b)?, dest(c)?),
+                Input(a) => Op::Input(dest(a)?),
+                Output(a) => Op::Output(arg(a)?),
+                JumpIfTrue(a, b) => Op::JumpIfTrue(arg(a)?, arg(b)?),
+                JumpIfFalse(a, b) => Op::JumpIfFalse(arg(a)?, arg(b)?),
+                LessThan(a, b, c) => Op::LessThan(arg(a)?, arg(b)?, dest(c)?),
+                Equals(a, b, c) => Op::Equals(arg(a)?, arg(b)?, dest(c)?),
+                AdjustRelativeBase(a) => Op::AdjustRelativeBase(arg(a)?),
+                Halt | Custom(..) => return None,
+            };
+            Some(op)
+        };
+        op().unwrap_or(Op::Interpret)
+    }
+}
+
+/// Decoded instructions indexed by the address they start at.
+///
+/// Instructions are only stored once they've been decoded twice, and slots
+/// only hold an index into `ops`, keeping the cost low for programs which
+/// run most of their instructions just once.
+#[derive(Clone, Debug, Default)]
+pub(super) struct DecodeCache {
+    /// For each address, `UNSEEN`, `SEEN` once an instruction there has
+    /// been decoded, or one more than the index of its cached instruction.
+    slots: Vec<u32>,
+    ops: Vec<Option<Op>>,
+}
+
+const UNSEEN: u32 = 0;
+const SEEN: u32 = u32::MAX;
+
+impl DecodeCache {
+    fn get(&self, ip: usize) -> Option<Op> {
+        match self.slots.get(ip) {
+            Some(&UNSEEN) | Some(&SEEN) | None => None,
+            Some(&idx) => self.ops[idx as usize - 1],
+        }
+    }
+
+    fn insert(&mut self, ip: usize, op: Op) {
+        if ip >= MAX_CACHED_ADDRESS {
+            return;
+        }
+        if ip >= self.slots.len() {
+            self.slots.resize(ip + 1, 0);
+        }
+        match self.slots[ip] {
+            UNSEEN => self.slots[ip] = SEEN,
+            SEEN => {
+                self.ops.push(Some(op));
+                self.slots[ip] = self.ops.len() as u32;
+            }
+            idx => self.ops[idx as usize - 1] = Some(op),
+        }
+    }
+
+    /// Drops every instruction which includes the word at `address`.
+    pub fn invalidate(&mut self, address: usize) {
+        let start = address.saturating_sub(Instruction::MAX_SIZE - 1);
+        let end = (address + 1).min(self.slots.len());
+        for &idx in self.slots.iter().take(end).skip(start) {
+            if idx != UNSEEN && idx != SEEN {
+                self.ops[idx as usize - 1] = None;
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.ops.clear();
+    }
+}
+
+/// Runs instructions directly against the machine's contiguous memory,
+/// decoding any the cache doesn't hold, and stops at the first one the
+/// interpreter needs to run instead, such as an access outside of `cells` or
+/// anything which would fault or block.
+pub(super) struct FastLoop<'a> {
+    pub cells: &'a mut [i64],
+    pub cache: &'a mut DecodeCache,
+    pub custom: &'a [CustomOpcode],
+    pub input: &'a mut VecDeque<i64>,
+    pub output: &'a mut Vec<i64>,
+    pub sink: Option<&'a mut Box<dyn OutputSink>>,
+    pub arithmetic: Arithmetic,
+    pub ip: usize,
+    pub relative_base: i64,
+}
+
+impl FastLoop<'_> {
+    /// Runs until an instruction needs the interpreter, returning the state
+    /// to stop in if the run ends first.
+    pub fn run(&mut self, limits: &mut Limits, pause_on_output: bool) -> Option<RunState> {
+        // Cover the whole of memory up front rather than growing a bit at a time
+        let len = self.cells.len().min(MAX_CACHED_ADDRESS);
+        if self.cache.slots.len() < len {
+            self.cache.slots.resize(len, UNSEEN);
+        }
+        loop {
+            if let Some(state) = limits.exceeded() {
+                return Some(state);
+            }
+            let output = self.step()?;
+            limits.retire();
+            if let Some(value) = output.filter(|_| pause_on_output) {
+                return Some(RunState::Output(value));
+            }
+        }
+    }
+
+    /// Executes the instruction at `ip`, returning any value it output, or
+    /// `None` without having any effect if the interpreter needs to run it.
+    fn step(&mut self) -> Option<Option<i64>> {
+        let op = match self.cache.get(self.ip) {
+            Some(op) => op,
+            None => self.decode()?,
+        };
+        let (cells, rb) = (&*self.cells, self.relative_base);
+        let (write, next) = match op {
+            Op::Add(a, b, c) => {
+                let sum = self
+                    .arithmetic
+                    .add(a.load(cells, rb)?, b.load(cells, rb)?)?;
+                ((c.address(cells, rb)?, sum), self.ip + 4)
+            }
+            Op::Mul(a, b, c) => {
+                let product = self
+                    .arithmetic
+                    .mul(a.load(cells, rb)?, b.load(cells, rb)?)?;
+                ((c.address(cells, rb)?, product), self.ip + 4)
+            }
+            Op::Input(a) => {
+                let address = a.address(cells, rb)?;
+                ((address, self.input.pop_front()?), self.ip + 2)
+            }
+            Op::Output(a) => {
+                let value = a.load(cells, rb)?;
+                match self.sink.as_mut() {
+                    Some(sink) => sink.emit(value),
+                    None => self.output.push(value),
+                }
+                self.ip += 2;
+                return Some(Some(value));
+            }
+            Op::JumpIfTrue(a, b) => {
+                self.ip = match a.load(cells, rb)? {
+                    0 => self.ip + 3,
+                    _ => target(b.load(cells, rb)?)?,
+                };
+                return Some(None);
+            }
+            Op::JumpIfFalse(a, b) => {
+                self.ip = match a.load(cells, rb)? {
+                    0 => target(b.load(cells, rb)?)?,
+                    _ => self.ip + 3,
+                };
+                return Some(None);
+            }
+            Op::LessThan(a, b, c) => {
+                let value = a.load(cells, rb)? < b.load(cells, rb)?;
+                ((c.address(cells, rb)?, value.into()), self.ip + 4)
+            }
+            Op::Equals(a, b, c) => {
+                let value = a.load(cells, rb)? == b.load(cells, rb)?;
+                ((c.address(cells, rb)?, value.into()), self.ip + 4)
+            }
+            Op::AdjustRelativeBase(a) => {
+                self.relative_base = self.arithmetic.add(rb, a.load(cells, rb)?)?;
+                self.ip += 2;
+                return Some(None);
+            }
+            Op::Interpret => return None,
+        };
+
+        let (address, value) = write;
+        self.cells[address] = value;
+        self.cache.invalidate(address);
+        self.ip = next;
+        Some(None)
+    }
+
+    fn decode(&mut self) -> Option<Op> {
+        let cells = &*self.cells;
+        let instruction =
+            Instruction::decode_with(self.ip, |a| cells.get(a).copied(), self.custom)?;
+        let op = Op::new(instruction);
+        self.cache.insert(self.ip, op);
+        Some(op)
+    }
+}
+
+fn target(address: i64) -> Option<usize> {
+    if address < 0 {
+        None
+    } else {
+        Some(address as usize)
+    }
+}
+
+#[test]
+fn engines_match() {
+    use crate::{IntcodeComputer, RunState};
+
+    let day05 = include_str!("../../input/day05.txt");
+    let day09 = include_str!("../../input/day09.txt");
+    let parse = |source: &str| source.parse::<crate::Program>().unwrap().into_words();
+
+    // Increments the immediate operand of its own output instruction
+    let counter = crate::assemble(
+        "
+        loop: out #0
+              add [loop+1], #1, [loop+1]
+              lt [loop+1], #5, [flag]
+              jt [flag], #loop
+              hlt
+        flag: data 0
+        ",
+    )
+    .unwrap();
+
+    let cases = [
+        (counter, vec![]),
+        (parse(day05), vec![1]),
+        (parse(day05), vec![5]),
+        (parse(day09), vec![1]),
+        (parse(day09), vec![2]),
+    ];
+    for (program, input) in cases.iter() {
+        let run = |engine: Engine, pause_on_output: bool| {
+            let mut computer = IntcodeComputer::new(program);
+            computer.set_engine(engine);
+            for &value in input {
+                computer.input(value);
+            }
+            let state = if pause_on_output {
+                loop {
+                    match computer.run_to_output() {
+                        Ok(RunState::Output(_)) => {}
+                        state => break state,
+                    }
+                }
+            } else {
+                computer.run()
+            };
+            assert_eq!(state, Ok(RunState::Halted));
+            let memory = computer.memory().read_range(0, computer.memory().extent());
+            (computer.output().to_vec(), memory, computer.ip())
+        };
+        let expected = run(Engine::Interpreter, false);
+        assert_eq!(run(Engine::Cached, false), expected);
+        assert_eq!(run(Engine::Cached, true), expected);
+    }
+
+    // Faults are reported identically too
+    for engine in [Engine::Interpreter, Engine::Cached].iter() {
+        // Turns its own add into an invalid instruction on the first pass
+        let mut computer = IntcodeComputer::new(&[1001, 0, 98, 0, 1105, 1, 0]);
+        computer.set_engine(*engine);
+        let err = computer.run().unwrap_err();
+        assert_eq!(err.context().ip, 0);
+        assert_eq!(err.context().word, 1099);
+    }
+}
@@ -0,0 +1,297 @@
+use super::{disassemble, Instruction, Item, Operand};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
+
+/// Where control can go after a basic block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Successor {
+    /// The following address, either by falling through or when a
+    /// conditional jump isn't taken.
+    Next(usize),
+    /// The target of a jump with an immediate target.
+    Jump(usize),
+    /// The target of a jump read from memory, which can't be resolved
+    /// statically.
+    Unknown,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BasicBlock {
+    pub instructions: Vec<(usize, Instruction)>,
+    pub successors: Vec<Successor>,
+}
+
+impl BasicBlock {
+    pub fn start(&self) -> usize {
+        self.instructions[0].0
+    }
+
+    /// The address following the block's last instruction.
+    pub fn end(&self) -> usize {
+        let (address, instruction) = self.instructions[self.instructions.len() - 1];
+        address + instruction.size()
+    }
+}
+
+/// The basic blocks of a program, found without running it.
+///
+/// Exploration starts from address zero and from where a linear disassembly
+/// suggests code starts. The address following every jump is explored too,
+/// as it's usually a return address reached through an unknown jump.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ControlFlowGraph {
+    blocks: BTreeMap<usize, BasicBlock>,
+}
+
+impl ControlFlowGraph {
+    pub fn new(program: &[i64]) -> Self {
+        let read = |address: usize| program.get(address).copied();
+
+        // Besides address zero, start from every static jump target and every
+        // instruction which follows data or a halt, as those are likely to be
+        // reached through a jump table or a computed jump
+        let mut work = vec![0];
+        let mut after_code = false;
+        for item in disassemble(program).items() {
+            match item.item {
+                Item::Instruction(instruction) => {
+                    if !after_code {
+                        work.push(item.address);
+                    }
+                    work.extend(jump_target(instruction));
+                    after_code = instruction != Instruction::Halt;
+                }
+                Item::Data(_) => after_code = false,
+            }
+        }
+
+        let mut instructions = BTreeMap::new();
+        let mut targets = work.iter().copied().collect::<BTreeSet<_>>();
+        while let Some(address) = work.pop() {
+            if instructions.contains_key(&address) {
+                continue;
+            }
+            let instruction = match Instruction::decode(address, read) {
+                Some(instruction) => instruction,
+                None => continue,
+            };
+            instructions.insert(address, instruction);
+
+            let next = address + instruction.size();
+            match instruction {
+                Instruction::Halt => {}
+                Instruction::JumpIfTrue(..) | Instruction::JumpIfFalse(..) => {
+                    targets.extend(jump_target(instruction));
+                    work.extend(jump_target(instruction));
+                    targets.insert(next);
+                    work.push(next);
+                }
+                _ => work.push(next),
+            }
+        }
+
+        let fallthroughs = instructions
+            .iter()
+            .filter(|(_, instruction)| !ends_block(**instruction))
+            .map(|(&address, instruction)| address + instruction.size())
+            .collect::<BTreeSet<_>>();
+        let leaders = instructions
+            .keys()
+            .filter(|address| targets.contains(address) || !fallthroughs.contains(address))
+            .copied()
+            .collect::<BTreeSet<_>>();
+
+        let mut blocks = BTreeMap::new();
+        for &leader in &leaders {
+            let mut block = BasicBlock {
+                instructions: Vec::new(),
+                successors: Vec::new(),
+            };
+            let mut address = leader;
+            loop {
+                let instruction = instructions[&address];
+                block.instructions.push((address, instruction));
+                let next = address + instruction.size();
+                if let Instruction::JumpIfTrue(_, target) | Instruction::JumpIfFalse(_, target) =
+                    instruction
+                {
+                    if taken(instruction) != Some(false) {
+                        block.successors.extend(match target {
+                            Operand::Immediate(target) if target >= 0 => {
+                                Some(Successor::Jump(target as usize))
+                            }
+                            Operand::Immediate(_) => None,
+                            _ => Some(Successor::Unknown),
+                        });
+                    }
+                    if !ends_block(instruction) {
+                        block.successors.push(Successor::Next(next));
+                    }
+                    break;
+                }
+                if instruction == Instruction::Halt {
+                    break;
+                }
+                if !instructions.contains_key(&next) || leaders.contains(&next) {
+                    block.successors.push(Successor::Next(next));
+                    break;
+                }
+                address = next;
+            }
+            blocks.insert(leader, block);
+        }
+
+        Self { blocks }
+    }
+
+    /// Basic blocks sorted by start address.
+    pub fn blocks(&self) -> impl Iterator<Item = &BasicBlock> {
+        self.blocks.values()
+    }
+
+    /// The basic block starting at `address`, if any.
+    pub fn block(&self, address: usize) -> Option<&BasicBlock> {
+        self.blocks.get(&address)
+    }
+
+    /// Writes the graph in Graphviz DOT format, with a node per block
+    /// labelled by its disassembly. Taken jumps are labelled, and edges to
+    /// unknown targets or to addresses outside any block lead to a shared
+    /// `?` node.
+    pub fn write_dot(&self, mut w: impl Write) -> io::Result<()> {
+        writeln!(w, "digraph cfg {{")?;
+        writeln!(w, "    node [shape=box, fontname=monospace];")?;
+        for (&start, block) in &self.blocks {
+            let label = block
+                .instructions
+                .iter()
+                .map(|(address, instruction)| format!("{:05}: {}\\l", address, instruction))
+                .collect::<String>();
+            writeln!(
+                w,
+                "    b{} [label=\"{}\"];",
+                start,
+                label.replace('"', "\\\"")
+            )?;
+        }
+
+        let mut unknown = false;
+        for (&start, block) in &self.blocks {
+            for &successor in &block.successors {
+                let (target, label) = match successor {
+                    Successor::Next(next) => (Some(next), None),
+                    Successor::Jump(target) => (Some(target), Some("jump")),
+                    Successor::Unknown => (None, None),
+                };
+                match target {
+                    Some(target) if self.blocks.contains_key(&target) => match label {
+                        Some(label) => {
+                            writeln!(w, "    b{} -> b{} [label=\"{}\"];", start, target, label)?
+                        }
+                        None => writeln!(w, "    b{} -> b{};", start, target)?,
+                    },
+                    Some(target) => {
+                        unknown = true;
+                        let label = label
+                            .map_or(target.to_string(), |label| format!("{} {}", label, target));
+                        writeln!(
+                            w,
+                            "    b{} -> unknown [label=\"{}\", style=dashed];",
+                            start, label
+                        )?
+                    }
+                    None => {
+                        unknown = true;
+                        writeln!(w, "    b{} -> unknown [style=dashed];", start)?
+                    }
+                }
+            }
+        }
+        if unknown {
+            writeln!(w, "    unknown [label=\"?\", shape=circle];")?;
+        }
+        writeln!(w, "}}")
+    }
+}
+
+/// The immediate, non-negative target of a jump.
+fn jump_target(instruction: Instruction) -> Option<usize> {
+    match instruction {
+        Instruction::JumpIfTrue(_, Operand::Immediate(target))
+        | Instruction::JumpIfFalse(_, Operand::Immediate(target))
+            if target >= 0 =>
+        {
+            Some(target as usize)
+        }
+        _ => None,
+    }
+}
+
+/// Whether a jump is always or never taken, if its condition is immediate.
+fn taken(instruction: Instruction) -> Option<bool> {
+    match instruction {
+        Instruction::JumpIfTrue(Operand::Immediate(cond), _) => Some(cond != 0),
+        Instruction::JumpIfFalse(Operand::Immediate(cond), _) => Some(cond == 0),
+        _ => None,
+    }
+}
+
+/// Whether control never falls through `instruction` to the next.
+pub(super) fn ends_block(instruction: Instruction) -> bool {
+    instruction == Instruction::Halt || taken(instruction) == Some(true)
+}
+
+#[test]
+fn cfg_call_and_return() {
+    // Calls a subroutine which doubles its argument, returning through a
+    // jump to an address held in memory
+    let program = crate::assemble(
+        "
+              in [x]
+              add #back, #0, [ret]
+              jt #1, #double
+        back: out [x]
+              hlt
+        double: mul [x], #2, [x]
+              lt [x], #100, [flag]
+              jt [flag], #double
+              jf #0, [ret]
+        x:    data 0
+        ret:  data 0
+        flag: data 0
+        ",
+    )
+    .unwrap();
+
+    let cfg = ControlFlowGraph::new(&program);
+    let starts = cfg.blocks().map(BasicBlock::start).collect::<Vec<_>>();
+    assert_eq!(starts, [0, 9, 12, 23]);
+
+    let entry = cfg.block(0).unwrap();
+    assert_eq!((entry.instructions.len(), entry.end()), (3, 9));
+    assert_eq!(entry.successors, [Successor::Jump(12)]);
+    assert!(cfg.block(9).unwrap().successors.is_empty());
+
+    // The loop back to itself is resolved, while the return isn't
+    let double = &cfg.block(12).unwrap().successors;
+    assert_eq!(double, &[Successor::Jump(12), Successor::Next(23)]);
+    assert_eq!(cfg.block(23).unwrap().successors, [Successor::Unknown]);
+
+    let mut dot = Vec::new();
+    cfg.write_dot(&mut dot).unwrap();
+    let dot = String::from_utf8(dot).unwrap();
+    assert!(dot.starts_with("digraph cfg {\n"));
+    assert!(dot.contains("    b0 -> b12 [label=\"jump\"];\n"));
+    assert!(dot.contains("    b23 -> unknown [style=dashed];\n"));
+    assert!(dot.contains("    b9 [label=\"00009: out [26]\\l00011: hlt\\l\"];\n"));
+
+    // Jumps out of the program don't get a node of their own
+    let mut dot = Vec::new();
+    ControlFlowGraph::new(&[1105, 1, 50, 99])
+        .write_dot(&mut dot)
+        .unwrap();
+    let dot = String::from_utf8(dot).unwrap();
+    assert!(!dot.contains("b50"), "{}", dot);
+    assert!(dot.contains("    b0 -> unknown [label=\"jump 50\", style=dashed];\n"));
+    assert!(dot.contains("    unknown [label=\"?\", shape=circle];\n"));
+}
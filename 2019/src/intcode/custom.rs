@@ -0,0 +1,190 @@
+use super::{Instruction, Opcode};
+use std::fmt;
+use std::sync::Arc;
+
+/// An opcode added to a machine by `Builder::opcode`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CustomOpcode {
+    /// The numeric opcode, which must not clash with a built-in one.
+    pub code: i64,
+    pub mnemonic: &'static str,
+    /// Number of parameters, at most 3.
+    pub arity: usize,
+    /// Index of the parameter the handler's result is written to, if any.
+    pub destination: Option<usize>,
+}
+
+/// Called with the values of a custom instruction's source parameters,
+/// returning the value to write to its destination. An error faults the
+/// machine with `IntcodeError::Handler`.
+pub type Handler = dyn Fn(&[i64]) -> Result<i64, String> + Send + Sync;
+
+/// The custom opcodes registered on a machine along with their handlers.
+#[derive(Clone, Default)]
+pub(super) struct OpcodeTable {
+    opcodes: Vec<CustomOpcode>,
+    handlers: Vec<Arc<Handler>>,
+}
+
+impl OpcodeTable {
+    pub fn register(&mut self, opcode: CustomOpcode, handler: Arc<Handler>) {
+        assert!(
+            (0..100).contains(&opcode.code) && Opcode::from_code(opcode.code).is_none(),
+            "opcode {} is reserved",
+            opcode.code
+        );
+        assert!(
+            self.get(opcode.code).is_none(),
+            "opcode {} is already registered",
+            opcode.code
+        );
+        assert!(
+            opcode.arity < Instruction::MAX_SIZE,
+            "opcode {} has too many parameters",
+            opcode.code
+        );
+        assert!(
+            opcode.destination.is_none_or(|idx| idx < opcode.arity),
+            "opcode {} writes to a missing parameter",
+            opcode.code
+        );
+        self.opcodes.push(opcode);
+        self.handlers.push(handler);
+    }
+
+    pub fn opcodes(&self) -> &[CustomOpcode] {
+        &self.opcodes
+    }
+
+    pub fn get(&self, code: i64) -> Option<CustomOpcode> {
+        self.opcodes
+            .iter()
+            .copied()
+            .find(|opcode| opcode.code == code)
+    }
+
+    pub fn handler(&self, code: i64) -> &Handler {
+        let idx = self.opcodes.iter().position(|opcode| opcode.code == code);
+        &*self.handlers[idx.expect("unregistered opcode")]
+    }
+}
+
+impl fmt::Debug for OpcodeTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(&self.opcodes).finish()
+    }
+}
+
+#[test]
+fn harness_opcodes() {
+    use crate::{IntcodeComputer, IntcodeError, Operand, RunState};
+    use std::sync::Mutex;
+
+    // `dbg` records its argument, `assert` faults unless both arguments are
+    // equal, and `max` writes the larger of two values
+    let dbg = CustomOpcode {
+        code: 50,
+        mnemonic: "dbg",
+        arity: 1,
+        destination: None,
+    };
+    let assert = CustomOpcode {
+        code: 51,
+        mnemonic: "assert",
+        arity: 2,
+        destination: None,
+    };
+    let max = CustomOpcode {
+        code: 52,
+        mnemonic: "max",
+        arity: 3,
+        destination: Some(2),
+    };
+    // Outputs the larger of its input and 7, asserting that it's 7
+    let source = "
+        in [100]
+        max [100], #7, [101]
+        dbg [101]
+        assert [101], #7
+        out [101]
+        hlt
+    ";
+    assert!(crate::assemble(source).is_err());
+    let program = crate::assemble_with(source, &[dbg, assert, max]).unwrap();
+    assert_eq!(
+        program,
+        [3, 100, 1052, 100, 7, 101, 50, 101, 1051, 101, 7, 4, 101, 99]
+    );
+    let listing = crate::disassemble_with(&program, &[dbg, assert, max]);
+    assert_eq!(
+        listing.at(6).unwrap().item,
+        crate::Item::Instruction(Instruction::Custom(
+            dbg,
+            [
+                Operand::Position(101),
+                Operand::Position(0),
+                Operand::Position(0)
+            ]
+        ))
+    );
+
+    let printed = Arc::new(Mutex::new(Vec::new()));
+    let log = printed.clone();
+    let mut computer = IntcodeComputer::builder()
+        .opcode(dbg, move |args| {
+            log.lock().unwrap().push(args[0]);
+            Ok(0)
+        })
+        .opcode(assert, |args| match args {
+            [a, b] if a == b => Ok(0),
+            [a, b] => Err(format!("{} != {}", a, b)),
+            _ => unreachable!(),
+        })
+        .opcode(max, |args| Ok(args[0].max(args[1])))
+        .build(&program);
+
+    assert_eq!(computer.custom_opcodes(), [dbg, assert, max]);
+    computer.start_profile();
+    computer.input(4);
+    assert_eq!(computer.run().unwrap(), RunState::Halted);
+    assert_eq!(computer.output(), [7]);
+    assert_eq!(*printed.lock().unwrap(), [7]);
+
+    let mut report = Vec::new();
+    let profile = computer.take_profile().unwrap();
+    profile
+        .write_report(&mut report, computer.memory(), 6)
+        .unwrap();
+    let report = String::from_utf8(report).unwrap();
+    assert!(report.contains("max [100], #7, [101]"), "{}", report);
+    assert!(!report.contains(".data"), "{}", report);
+
+    let instruction = Instruction::Custom(
+        max,
+        [
+            Operand::Position(100),
+            Operand::Immediate(7),
+            Operand::Position(101),
+        ],
+    );
+    assert_eq!(instruction.to_string(), "max [100], #7, [101]");
+    assert_eq!(instruction.destination(), Some(Operand::Position(101)));
+
+    // Clones share handlers
+    let mut clone = computer.clone();
+    clone.reset(&program);
+    clone.input(8);
+    match clone.run() {
+        Err(IntcodeError::Handler { message, context }) => {
+            assert_eq!((message.as_str(), context.ip), ("8 != 7", 8));
+            assert_eq!(context.opcode, Some(Opcode::Custom(assert)));
+        }
+        result => panic!("unexpected result {:?}", result),
+    }
+    assert_eq!(*printed.lock().unwrap(), [7, 8]);
+
+    // Unknown to machines without the opcode
+    let mut plain = IntcodeComputer::new(&program);
+    plain.input(4);
+    assert_eq!(plain.run().unwrap_err().context().ip, 2);
+}
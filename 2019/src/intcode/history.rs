@@ -0,0 +1,127 @@
+use super::RunState;
+use std::collections::VecDeque;
+
+/// Everything needed to undo a single instruction.
+#[derive(Clone, Debug)]
+pub(super) struct Change {
+    pub ip: usize,
+    pub relative_base: i64,
+    pub state: RunState,
+    /// The address written to along with the value it held beforehand.
+    pub write: Option<(usize, i64)>,
+    /// The value consumed by an input instruction.
+    pub input: Option<i64>,
+    /// Length of the output buffer before the instruction executed.
+    pub output_len: usize,
+}
+
+/// An undo log holding changes for at most `budget` instructions, discarding
+/// the oldest first.
+#[derive(Clone, Debug)]
+pub(super) struct History {
+    changes: VecDeque<Change>,
+    budget: usize,
+}
+
+impl History {
+    pub fn new(budget: usize) -> Self {
+        Self {
+            changes: VecDeque::new(),
+            budget,
+        }
+    }
+
+    pub fn push(&mut self, change: Change) {
+        if self.budget == 0 {
+            return;
+        }
+        if self.changes.len() == self.budget {
+            self.changes.pop_front();
+        }
+        self.changes.push_back(change);
+    }
+
+    pub fn pop(&mut self) -> Option<Change> {
+        self.changes.pop_back()
+    }
+
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.changes.clear()
+    }
+}
+
+#[test]
+fn rewind() {
+    use crate::IntcodeComputer;
+
+    // Reads two values and outputs whether they're equal, then halts
+    let program = crate::assemble(
+        "
+        in [a]
+        in [b]
+        eq [a], [b], [a]
+        out [a]
+        hlt
+        a: data 0
+        b: data 0
+        ",
+    )
+    .unwrap();
+
+    let mut computer = IntcodeComputer::new(&program);
+    computer.enable_history(100);
+    computer.input(8);
+    computer.input(8);
+    assert_eq!(computer.run().unwrap(), RunState::Halted);
+    assert_eq!(computer.output(), [1]);
+    assert_eq!(computer.history_len(), 5);
+
+    // Back out of the halt and the output
+    assert!(computer.step_back());
+    assert_eq!(computer.status(), RunState::Paused);
+    assert!(computer.step_back());
+    assert!(computer.output().is_empty());
+
+    // Rewind the comparison and the second input, then replay with another value
+    assert!(computer.step_back());
+    assert!(computer.step_back());
+    assert_eq!(computer.ip(), 2);
+    assert_eq!(computer.memory().read_range(0, program.len()), {
+        let mut memory = program.clone();
+        memory[program.len() - 2] = 8;
+        memory
+    });
+    computer.step().unwrap();
+    assert_eq!(computer.memory().read(program.len() - 1), 8);
+    assert!(computer.step_back());
+
+    assert!(computer.step_back());
+    assert!(!computer.step_back());
+    assert_eq!(computer.ip(), 0);
+    assert_eq!(computer.memory().read_range(0, program.len()), program);
+
+    // Rewound input is consumed again
+    assert_eq!(computer.run().unwrap(), RunState::Halted);
+    assert_eq!(computer.output(), [1]);
+
+    // Only the most recent instructions are kept
+    computer.reset(&program);
+    computer.enable_history(2);
+    computer.input(1);
+    computer.input(2);
+    assert_eq!(computer.run().unwrap(), RunState::Halted);
+    assert_eq!(computer.output(), [0]);
+    assert!(computer.step_back());
+    assert!(computer.step_back());
+    assert!(!computer.step_back());
+    assert_eq!(computer.ip(), 8);
+
+    // Blocking on input doesn't check the destination
+    let mut computer = IntcodeComputer::new(&[3, -1, 99]);
+    computer.enable_history(2);
+    assert_eq!(computer.run().unwrap(), RunState::NeedsInput);
+}
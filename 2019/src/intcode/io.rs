@@ -0,0 +1,185 @@
+use std::collections::VecDeque;
+use std::sync::mpsc::{Receiver, Sender, SyncSender};
+use std::sync::{Arc, Mutex};
+
+/// A source of input values for an `IntcodeComputer`.
+pub trait InputSource: Send {
+    /// Returns the next value, or `None` if no input is available yet.
+    fn next_input(&mut self) -> Option<i64>;
+}
+
+/// A destination for values output by an `IntcodeComputer`.
+pub trait OutputSink: Send {
+    fn emit(&mut self, value: i64);
+}
+
+impl InputSource for VecDeque<i64> {
+    fn next_input(&mut self) -> Option<i64> {
+        self.pop_front()
+    }
+}
+
+impl<F: FnMut() -> Option<i64> + Send> InputSource for F {
+    fn next_input(&mut self) -> Option<i64> {
+        self()
+    }
+}
+
+/// Blocks until a value is received, returning `None` once all senders are gone.
+impl InputSource for Receiver<i64> {
+    fn next_input(&mut self) -> Option<i64> {
+        self.recv().ok()
+    }
+}
+
+/// Adapts any iterator of values into an `InputSource`.
+pub struct IterInput<I>(pub I);
+
+impl<I: Iterator<Item = i64> + Send> InputSource for IterInput<I> {
+    fn next_input(&mut self) -> Option<i64> {
+        self.0.next()
+    }
+}
+
+impl OutputSink for Vec<i64> {
+    fn emit(&mut self, value: i64) {
+        self.push(value)
+    }
+}
+
+impl OutputSink for VecDeque<i64> {
+    fn emit(&mut self, value: i64) {
+        self.push_back(value)
+    }
+}
+
+impl<F: FnMut(i64) + Send> OutputSink for F {
+    fn emit(&mut self, value: i64) {
+        self(value)
+    }
+}
+
+/// Values sent after the receiver has hung up are dropped.
+impl OutputSink for Sender<i64> {
+    fn emit(&mut self, value: i64) {
+        let _ = self.send(value);
+    }
+}
+
+impl OutputSink for SyncSender<i64> {
+    fn emit(&mut self, value: i64) {
+        let _ = self.send(value);
+    }
+}
+
+/// A shared FIFO which can be attached as the output of one computer and the
+/// input of another, connecting them directly.
+#[derive(Clone, Debug, Default)]
+pub struct Pipe(Arc<Mutex<VecDeque<i64>>>);
+
+impl Pipe {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&self, value: i64) {
+        self.0.lock().unwrap().push_back(value)
+    }
+
+    pub fn pop(&self) -> Option<i64> {
+        self.0.lock().unwrap().pop_front()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl InputSource for Pipe {
+    fn next_input(&mut self) -> Option<i64> {
+        self.pop()
+    }
+}
+
+impl OutputSink for Pipe {
+    fn emit(&mut self, value: i64) {
+        self.push(value)
+    }
+}
+
+#[test]
+fn sources_and_sinks() {
+    use crate::IntcodeComputer;
+    use std::sync::mpsc;
+
+    // Echoes two inputs, doubled
+    let program = crate::assemble(
+        "
+        in [100]
+        mul [100], #2, [100]
+        out [100]
+        in [100]
+        mul [100], #2, [100]
+        out [100]
+        hlt
+        ",
+    )
+    .unwrap();
+
+    let mut computer = IntcodeComputer::new(&program);
+    computer.attach_input(IterInput(vec![1, 2].into_iter()));
+    let (tx, rx) = mpsc::channel();
+    computer.attach_output(tx);
+    computer.run().unwrap();
+    assert_eq!(rx.try_iter().collect::<Vec<_>>(), [2, 4]);
+    assert!(computer.output().is_empty());
+
+    let mut computer = IntcodeComputer::new(&program);
+    let mut n = 0;
+    computer.attach_input(move || {
+        n += 5;
+        Some(n)
+    });
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = seen.clone();
+    computer.attach_output(move |value| sink.lock().unwrap().push(value));
+    computer.run().unwrap();
+    assert_eq!(*seen.lock().unwrap(), [10, 20]);
+}
+
+#[test]
+fn pipes() {
+    use crate::{IntcodeComputer, RunState};
+
+    // Adds one to every input
+    let program = crate::assemble(
+        "
+        loop: in [100]
+              add [100], #1, [100]
+              out [100]
+              jt #1, #loop
+        ",
+    )
+    .unwrap();
+
+    let (a_to_b, b_to_a) = (Pipe::new(), Pipe::new());
+    let mut a = IntcodeComputer::new(&program);
+    a.attach_input(b_to_a.clone());
+    a.attach_output(a_to_b.clone());
+    let mut b = IntcodeComputer::new(&program);
+    b.attach_input(a_to_b.clone());
+    b.attach_output(b_to_a.clone());
+
+    b_to_a.push(0);
+    for _ in 0..3 {
+        for c in &mut [&mut a, &mut b] {
+            assert_eq!(c.run().unwrap(), RunState::NeedsInput);
+        }
+    }
+    assert_eq!(b_to_a.pop(), Some(6));
+    assert!(a_to_b.is_empty());
+}
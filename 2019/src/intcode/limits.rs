@@ -0,0 +1,99 @@
+use super::RunState;
+use std::time::{Duration, Instant};
+
+/// How many instructions run between checks of the clock.
+const CLOCK_INTERVAL: u32 = 1024;
+
+/// The instruction budget and deadline for a single run, both counted from
+/// when the run started.
+pub(super) struct Limits {
+    remaining: Option<u64>,
+    deadline: Option<Instant>,
+    until_clock: u32,
+}
+
+impl Limits {
+    pub fn start(budget: Option<u64>, timeout: Option<Duration>) -> Self {
+        Self {
+            remaining: budget,
+            deadline: timeout.map(|timeout| Instant::now() + timeout),
+            until_clock: 0,
+        }
+    }
+
+    /// The state to stop in before executing another instruction, if either
+    /// limit has been exceeded. The clock is only read every so often.
+    pub fn exceeded(&mut self) -> Option<RunState> {
+        if self.remaining == Some(0) {
+            return Some(RunState::BudgetExhausted);
+        }
+        if let Some(deadline) = self.deadline {
+            if self.until_clock == 0 {
+                if Instant::now() >= deadline {
+                    return Some(RunState::TimedOut);
+                }
+                self.until_clock = CLOCK_INTERVAL;
+            }
+            self.until_clock -= 1;
+        }
+        None
+    }
+
+    /// Whether the budget allows at least `n` more instructions.
+    pub fn covers(&self, n: u64) -> bool {
+        self.remaining.is_none_or(|remaining| remaining >= n)
+    }
+
+    /// Counts an executed instruction against the budget.
+    pub fn retire(&mut self) {
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= 1;
+        }
+    }
+}
+
+#[test]
+fn runaway_programs() {
+    use crate::{Engine, IntcodeComputer};
+
+    // Counts to 2000, outputting the total
+    let counter = crate::assemble(
+        "
+        loop: add [n], #1, [n]
+              lt [n], #2000, [flag]
+              jt [flag], #loop
+              out [n]
+              hlt
+        n:    data 0
+        flag: data 0
+        ",
+    )
+    .unwrap();
+
+    for engine in [Engine::Interpreter, Engine::Cached].iter() {
+        let mut computer = IntcodeComputer::new(&counter);
+        computer.set_engine(*engine);
+        computer.set_budget(Some(1000));
+        assert_eq!(computer.run().unwrap(), RunState::BudgetExhausted);
+        assert_eq!(computer.memory().read(14), 334);
+
+        // Each run starts with a fresh budget
+        let mut exhausted = 1;
+        while computer.run().unwrap() == RunState::BudgetExhausted {
+            exhausted += 1;
+        }
+        assert_eq!((exhausted, computer.status()), (6, RunState::Halted));
+        assert_eq!(computer.output(), [2000]);
+
+        // Jumps to itself forever
+        let mut computer = IntcodeComputer::new(&[1105, 1, 0]);
+        computer.set_engine(*engine);
+        computer.set_timeout(Some(Duration::from_millis(10)));
+        assert_eq!(computer.run().unwrap(), RunState::TimedOut);
+        assert_eq!(computer.run_to_output().unwrap(), RunState::TimedOut);
+        computer.set_timeout(None);
+        computer.set_budget(Some(0));
+        assert_eq!(computer.run().unwrap(), RunState::BudgetExhausted);
+        assert_eq!(computer.ip(), 0);
+    }
+}
@@ -0,0 +1,333 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Backing storage for an `IntcodeComputer`.
+///
+/// Memory is unbounded: every address can be written and reading an address
+/// which was never written yields zero.
+pub trait Memory: Send {
+    fn read(&self, address: usize) -> i64;
+
+    fn write(&mut self, address: usize, value: i64);
+
+    /// One past the highest address which may hold a non-zero value, or
+    /// `usize::MAX` if that's the last address.
+    fn extent(&self) -> usize;
+
+    /// Clears memory and loads `program` starting at address zero.
+    fn load(&mut self, program: &[i64]);
+
+    fn box_clone(&self) -> Box<dyn Memory>;
+
+    /// The cells stored contiguously from address zero, if any, which hot
+    /// loops can access directly. Cells past the end still need `read` and
+    /// `write`.
+    fn cells_mut(&mut self) -> Option<&mut [i64]> {
+        None
+    }
+
+    /// Reads `len` cells from `start`, stopping early at the last address.
+    fn read_range(&self, start: usize, len: usize) -> Vec<i64> {
+        (0..len)
+            .map_while(|idx| start.checked_add(idx))
+            .map(|address| self.read(address))
+            .collect()
+    }
+
+    /// Contiguous runs of memory, by start address, which together cover
+    /// every non-zero cell.
+    fn segments(&self) -> Vec<(usize, Vec<i64>)> {
+        vec![(0, self.read_range(0, self.extent()))]
+    }
+}
+
+impl Clone for Box<dyn Memory> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+/// Highest address `DenseMemory` grows to cover contiguously.
+const DENSE_LIMIT: usize = 1 << 20;
+
+/// Contiguous memory which grows to cover the highest address written, up
+/// to `DENSE_LIMIT`. Cells past that are stored sparsely so that a stray
+/// write to a distant address can't exhaust memory.
+#[derive(Clone, Debug, Default)]
+pub struct DenseMemory {
+    cells: Vec<i64>,
+    distant: PagedMemory,
+}
+
+impl Memory for DenseMemory {
+    fn read(&self, address: usize) -> i64 {
+        match self.cells.get(address) {
+            Some(&value) => value,
+            None if address >= DENSE_LIMIT => self.distant.read(address),
+            None => 0,
+        }
+    }
+
+    fn write(&mut self, address: usize, value: i64) {
+        if address >= self.cells.len() {
+            if address >= DENSE_LIMIT {
+                return self.distant.write(address, value);
+            }
+            if value == 0 {
+                return;
+            }
+            self.cells.resize(address + 1, 0);
+        }
+        self.cells[address] = value;
+    }
+
+    fn extent(&self) -> usize {
+        self.distant.extent().max(self.cells.len())
+    }
+
+    fn segments(&self) -> Vec<(usize, Vec<i64>)> {
+        let mut segments = vec![(0, self.cells.clone())];
+        segments.extend(self.distant.segments());
+        segments
+    }
+
+    fn load(&mut self, program: &[i64]) {
+        let (near, far) = program.split_at(program.len().min(DENSE_LIMIT));
+        self.cells.clear();
+        self.cells.extend_from_slice(near);
+        self.distant.load(&[]);
+        for (idx, &value) in far.iter().enumerate() {
+            self.distant.write(DENSE_LIMIT + idx, value);
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn Memory> {
+        Box::new(self.clone())
+    }
+
+    fn cells_mut(&mut self) -> Option<&mut [i64]> {
+        Some(&mut self.cells)
+    }
+}
+
+const PAGE_SIZE: usize = 1 << 10;
+
+/// Sparse memory which only allocates the pages that have been written,
+/// suitable for programs which scatter data across distant addresses.
+#[derive(Clone, Debug, Default)]
+pub struct PagedMemory {
+    pages: HashMap<usize, Box<[i64; PAGE_SIZE]>>,
+}
+
+impl Memory for PagedMemory {
+    fn read(&self, address: usize) -> i64 {
+        self.pages
+            .get(&(address / PAGE_SIZE))
+            .map_or(0, |page| page[address % PAGE_SIZE])
+    }
+
+    fn write(&mut self, address: usize, value: i64) {
+        let page = match self.pages.get_mut(&(address / PAGE_SIZE)) {
+            Some(page) => page,
+            None if value == 0 => return,
+            None => self
+                .pages
+                .entry(address / PAGE_SIZE)
+                .or_insert_with(|| Box::new([0; PAGE_SIZE])),
+        };
+        page[address % PAGE_SIZE] = value;
+    }
+
+    fn extent(&self) -> usize {
+        self.pages
+            .keys()
+            .max()
+            .map_or(0, |page| (page + 1).saturating_mul(PAGE_SIZE))
+    }
+
+    fn segments(&self) -> Vec<(usize, Vec<i64>)> {
+        let mut segments = self
+            .pages
+            .iter()
+            .map(|(&page, cells)| (page * PAGE_SIZE, cells.to_vec()))
+            .collect::<Vec<_>>();
+        segments.sort_unstable_by_key(|&(start, _)| start);
+        segments
+    }
+
+    fn load(&mut self, program: &[i64]) {
+        self.pages.clear();
+        for (address, &value) in program.iter().enumerate() {
+            self.write(address, value);
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn Memory> {
+        Box::new(self.clone())
+    }
+}
+
+const COW_PAGE_SIZE: usize = 1 << 6;
+
+/// Memory over a program image shared between machines, which only copies
+/// the pages that are written. Cloning and reloading the same image cost
+/// time proportional to the pages written rather than the program size.
+#[derive(Clone, Debug, Default)]
+pub struct CowMemory {
+    image: Arc<[i64]>,
+    pages: HashMap<usize, Box<[i64; COW_PAGE_SIZE]>>,
+}
+
+impl CowMemory {
+    pub fn new(image: Arc<[i64]>) -> Self {
+        Self {
+            image,
+            pages: HashMap::new(),
+        }
+    }
+
+    pub fn image(&self) -> &Arc<[i64]> {
+        &self.image
+    }
+
+    /// Number of pages copied from the image or allocated past it.
+    pub fn touched(&self) -> usize {
+        self.pages.len()
+    }
+
+    fn image_page(&self, page: usize) -> &[i64] {
+        let start = (page * COW_PAGE_SIZE).min(self.image.len());
+        let end = (start + COW_PAGE_SIZE).min(self.image.len());
+        &self.image[start..end]
+    }
+}
+
+impl Memory for CowMemory {
+    fn read(&self, address: usize) -> i64 {
+        match self.pages.get(&(address / COW_PAGE_SIZE)) {
+            Some(page) => page[address % COW_PAGE_SIZE],
+            None => self.image.get(address).copied().unwrap_or(0),
+        }
+    }
+
+    fn write(&mut self, address: usize, value: i64) {
+        let idx = address / COW_PAGE_SIZE;
+        if !self.pages.contains_key(&idx) {
+            if self.read(address) == value {
+                return;
+            }
+            let mut page = Box::new([0; COW_PAGE_SIZE]);
+            let original = self.image_page(idx);
+            page[..original.len()].copy_from_slice(original);
+            self.pages.insert(idx, page);
+        }
+        self.pages.get_mut(&idx).unwrap()[address % COW_PAGE_SIZE] = value;
+    }
+
+    fn extent(&self) -> usize {
+        self.pages
+            .keys()
+            .map(|page| (page + 1).saturating_mul(COW_PAGE_SIZE))
+            .fold(self.image.len(), usize::max)
+    }
+
+    fn segments(&self) -> Vec<(usize, Vec<i64>)> {
+        let image_pages = self.image.len().div_ceil(COW_PAGE_SIZE);
+        let mut pages = self
+            .pages
+            .keys()
+            .copied()
+            .filter(|&page| page >= image_pages)
+            .chain(0..image_pages)
+            .collect::<Vec<_>>();
+        pages.sort_unstable();
+        pages
+            .into_iter()
+            .map(|page| {
+                let cells = match self.pages.get(&page) {
+                    Some(cells) => cells.to_vec(),
+                    None => self.image_page(page).to_vec(),
+                };
+                (page * COW_PAGE_SIZE, cells)
+            })
+            .collect()
+    }
+
+    /// Only drops the copied pages when `program` is the current image, such
+    /// as when resetting with the image itself.
+    fn load(&mut self, program: &[i64]) {
+        self.pages.clear();
+        if !std::ptr::eq(program, &*self.image) && program != &*self.image {
+            self.image = program.into();
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn Memory> {
+        Box::new(self.clone())
+    }
+}
+
+#[test]
+fn unbounded() {
+    let memories: [Box<dyn Memory>; 3] = [
+        Box::new(DenseMemory::default()),
+        Box::new(PagedMemory::default()),
+        Box::new(CowMemory::default()),
+    ];
+
+    for mut memory in memories.iter().cloned() {
+        memory.load(&[1, 2, 3]);
+        memory.write(100_000, 7);
+        memory.write(usize::MAX / 2, 0);
+
+        assert_eq!(memory.read_range(0, 4), [1, 2, 3, 0]);
+        assert_eq!(memory.read(100_000), 7);
+        assert_eq!(memory.read(usize::MAX), 0);
+        assert!(memory.extent() > 100_000);
+        let segments = memory.segments();
+        assert_eq!(segments[0].1[..3], [1, 2, 3]);
+        let (start, cells) = segments.last().unwrap();
+        assert_eq!(cells[100_000 - start], 7);
+
+        memory.write(usize::MAX, 1);
+        assert_eq!(memory.extent(), usize::MAX);
+        assert_eq!(memory.read_range(usize::MAX - 1, 4), [0, 1]);
+
+        memory.load(&[4]);
+        assert_eq!(memory.read(100_000), 0);
+        assert_eq!(memory.read(0), 4);
+    }
+}
+
+#[test]
+fn copy_on_write() {
+    let image: Arc<[i64]> = (0..200).collect::<Vec<_>>().into();
+    let mut memory = CowMemory::new(image.clone());
+    memory.write(10, 7);
+    memory.write(150, 150);
+    assert_eq!(memory.touched(), 1);
+
+    let mut clone = memory.clone();
+    clone.write(100, -1);
+    assert_eq!((memory.read(10), memory.read(100)), (7, 100));
+    assert_eq!((clone.read(10), clone.read(100)), (7, -1));
+    assert_eq!(memory.read_range(62, 4), [62, 63, 64, 65]);
+
+    let segments = clone.segments();
+    assert_eq!(segments.len(), 4);
+    assert_eq!(
+        segments[1],
+        (
+            64,
+            (64..128).map(|v| if v == 100 { -1 } else { v }).collect()
+        )
+    );
+
+    // Reloading the image keeps sharing it, while another program replaces it
+    clone.load(&image);
+    assert_eq!((clone.touched(), clone.read(100)), (0, 100));
+    assert!(Arc::ptr_eq(clone.image(), &image));
+    clone.load(&[1, 2]);
+    assert_eq!(clone.extent(), 2);
+    assert_eq!(Arc::strong_count(&image), 2);
+}
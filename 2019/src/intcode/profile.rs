@@ -0,0 +1,167 @@
+use super::{Disassembled, Instruction, Item, Memory, Opcode};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+/// Execution counts gathered while profiling was enabled.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Profile {
+    addresses: HashMap<usize, u64>,
+    opcodes: HashMap<Opcode, u64>,
+    inputs: u64,
+    outputs: u64,
+}
+
+impl Profile {
+    pub(super) fn record(&mut self, ip: usize, instruction: Instruction) {
+        *self.addresses.entry(ip).or_default() += 1;
+        let opcode = instruction.opcode();
+        *self.opcodes.entry(opcode).or_default() += 1;
+        match opcode {
+            Opcode::Input => self.inputs += 1,
+            Opcode::Output => self.outputs += 1,
+            _ => {}
+        }
+    }
+
+    /// Total number of instructions executed.
+    pub fn retired(&self) -> u64 {
+        self.opcodes.values().sum()
+    }
+
+    pub fn inputs(&self) -> u64 {
+        self.inputs
+    }
+
+    pub fn outputs(&self) -> u64 {
+        self.outputs
+    }
+
+    /// Number of times the instruction at `address` was executed.
+    pub fn count_at(&self, address: usize) -> u64 {
+        self.addresses.get(&address).copied().unwrap_or(0)
+    }
+
+    pub fn opcode_count(&self, opcode: Opcode) -> u64 {
+        self.opcodes.get(&opcode).copied().unwrap_or(0)
+    }
+
+    /// Executed addresses along with their counts, most executed first.
+    pub fn hot_spots(&self) -> Vec<(usize, u64)> {
+        let mut hot_spots = self
+            .addresses
+            .iter()
+            .map(|(&address, &count)| (address, count))
+            .collect::<Vec<_>>();
+        hot_spots.sort_unstable_by_key(|&(address, count)| (Reverse(count), address));
+        hot_spots
+    }
+
+    /// Writes a summary followed by the `top` hottest addresses, disassembled
+    /// from `memory` along with any custom opcodes that were executed.
+    /// Self-modifying programs are shown as they are now, which may differ
+    /// from what was executed.
+    pub fn write_report(
+        &self,
+        mut w: impl Write,
+        memory: &dyn Memory,
+        top: usize,
+    ) -> io::Result<()> {
+        let retired = self.retired();
+        let percent = |count: u64| 100.0 * count as f64 / retired.max(1) as f64;
+
+        writeln!(w, "instructions retired: {}", retired)?;
+        writeln!(w, "inputs: {}", self.inputs)?;
+        writeln!(w, "outputs: {}", self.outputs)?;
+
+        writeln!(w)?;
+        writeln!(w, "{:<8} {:>12} {:>7}", "opcode", "count", "%")?;
+        let mut opcodes = self
+            .opcodes
+            .iter()
+            .map(|(&opcode, &count)| (opcode, count))
+            .collect::<Vec<_>>();
+        opcodes.sort_by_key(|&(opcode, count)| (Reverse(count), opcode.code()));
+        for (opcode, count) in opcodes {
+            writeln!(
+                w,
+                "{:<8} {:>12} {:>6.2}%",
+                opcode.mnemonic(),
+                count,
+                percent(count)
+            )?;
+        }
+
+        let custom = self
+            .opcodes
+            .keys()
+            .filter_map(|&opcode| match opcode {
+                Opcode::Custom(opcode) => Some(opcode),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        writeln!(w)?;
+        writeln!(w, "{:>12} {:>7}  instruction", "count", "%")?;
+        for (address, count) in self.hot_spots().into_iter().take(top) {
+            let read = |a: usize| Some(memory.read(a));
+            let item = match Instruction::decode_with(address, read, &custom) {
+                Some(instruction) => Disassembled {
+                    address,
+                    words: memory.read_range(address, instruction.size()),
+                    item: Item::Instruction(instruction),
+                },
+                None => Disassembled {
+                    address,
+                    words: vec![memory.read(address)],
+                    item: Item::Data(memory.read(address)),
+                },
+            };
+            writeln!(w, "{:>12} {:>6.2}%  {}", count, percent(count), item)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn profile_countdown() {
+    use crate::IntcodeComputer;
+
+    // Counts down from its input, outputting each value
+    let program = crate::assemble(
+        "
+              in [n]
+        loop: out [n]
+              add [n], #-1, [n]
+              jt [n], #loop
+              hlt
+        n:    data 0
+        ",
+    )
+    .unwrap();
+
+    let mut computer = IntcodeComputer::new(&program);
+    computer.start_profile();
+    computer.input(3);
+    computer.run().unwrap();
+    let profile = computer.take_profile().unwrap();
+
+    assert_eq!(profile.retired(), 1 + 3 * 3 + 1);
+    assert_eq!((profile.inputs(), profile.outputs()), (1, 3));
+    assert_eq!(profile.opcode_count(Opcode::Add), 3);
+    assert_eq!(profile.count_at(2), 3);
+    assert_eq!(profile.hot_spots()[..4], [(2, 3), (4, 3), (8, 3), (0, 1)]);
+
+    let mut report = Vec::new();
+    profile
+        .write_report(&mut report, computer.memory(), 2)
+        .unwrap();
+    let report = String::from_utf8(report).unwrap();
+    let lines = report.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "instructions retired: 11");
+    assert_eq!(lines[5], "add                 3  27.27%");
+    assert_eq!(
+        lines[lines.len() - 2],
+        "           3  27.27%  00002: 4,12                     out [12]"
+    );
+    assert_eq!(lines.len(), 14);
+}
@@ -0,0 +1,123 @@
+use std::fs;
+use std::io::{self, Read};
+use std::num::ParseIntError;
+use std::ops::Deref;
+use std::path::Path;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// An Intcode program in its comma separated text form.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Program(Vec<i64>);
+
+#[derive(Debug, Error)]
+pub enum ProgramError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("invalid word {token:?} at index {index}: {source}")]
+    InvalidWord {
+        index: usize,
+        token: String,
+        source: ParseIntError,
+    },
+    #[error("program has {len} words, more than the limit of {}", Program::MAX_LEN)]
+    TooLarge { len: usize },
+}
+
+impl Program {
+    /// Longest program accepted, which is far beyond any puzzle input.
+    pub const MAX_LEN: usize = 1 << 20;
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ProgramError> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    pub fn from_reader(mut r: impl Read) -> Result<Self, ProgramError> {
+        let mut source = String::new();
+        r.read_to_string(&mut source)?;
+        source.parse()
+    }
+
+    pub fn from_stdin() -> Result<Self, ProgramError> {
+        Self::from_reader(io::stdin())
+    }
+
+    pub fn into_words(self) -> Vec<i64> {
+        self.0
+    }
+}
+
+/// Whitespace, including newlines, is allowed around every word, and a
+/// trailing comma is ignored.
+impl FromStr for Program {
+    type Err = ProgramError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_suffix(',').unwrap_or(s);
+        if s.is_empty() {
+            return Ok(Program::default());
+        }
+
+        let len = s.matches(',').count() + 1;
+        if len > Self::MAX_LEN {
+            return Err(ProgramError::TooLarge { len });
+        }
+        let words = s
+            .split(',')
+            .enumerate()
+            .map(|(index, token)| {
+                let token = token.trim();
+                token.parse().map_err(|source| ProgramError::InvalidWord {
+                    index,
+                    token: token.to_owned(),
+                    source,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Program(words))
+    }
+}
+
+impl From<Vec<i64>> for Program {
+    fn from(words: Vec<i64>) -> Self {
+        Program(words)
+    }
+}
+
+impl Deref for Program {
+    type Target = [i64];
+
+    fn deref(&self) -> &[i64] {
+        &self.0
+    }
+}
+
+#[test]
+fn parse_programs() {
+    let parse = |s: &str| s.parse::<Program>();
+
+    assert_eq!(*parse("1,0,0,3,99\n").unwrap(), [1, 0, 0, 3, 99]);
+    assert_eq!(*parse(" 104, -1,\n 99 ,\n").unwrap(), [104, -1, 99]);
+    assert!(parse("\n").unwrap().is_empty());
+
+    match parse("1,2,x3,4").unwrap_err() {
+        ProgramError::InvalidWord { index, token, .. } => assert_eq!((index, &*token), (2, "x3")),
+        err => panic!("unexpected error {:?}", err),
+    }
+    let err = parse("1,,2").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid word \"\" at index 1: cannot parse integer from empty string"
+    );
+
+    let huge = vec!["0"; Program::MAX_LEN + 1].join(",");
+    assert!(matches!(
+        parse(&huge),
+        Err(ProgramError::TooLarge { len }) if len == Program::MAX_LEN + 1
+    ));
+
+    let day09 = Program::from_file("input/day09.txt").unwrap();
+    assert_eq!(day09[..3], [1102, 34463338, 34463338]);
+    assert_eq!(Program::from_reader(&b"3,0,4,0,99"[..]).unwrap().len(), 5);
+}
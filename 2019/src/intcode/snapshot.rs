@@ -0,0 +1,267 @@
+use super::RunState;
+use std::io::{self, BufRead, Write};
+use thiserror::Error;
+
+const HEADER: &str = "intcode-snapshot";
+const VERSION: u32 = 1;
+
+/// The complete state of an `IntcodeComputer` at a point between
+/// instructions.
+///
+/// Snapshots are stored as line-oriented text starting with a version header:
+///
+/// ```text
+/// intcode-snapshot 1
+/// ip 2
+/// relative-base 1
+/// status output 109
+/// input 5,6
+/// output 109
+/// memory 0 109,1,204,-1,99
+/// ```
+///
+/// Memory is written as one or more `memory <address> <values>` segments and
+/// every cell not covered by a segment is zero.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    pub ip: usize,
+    pub relative_base: i64,
+    pub status: RunState,
+    /// Input which has been queued but not yet consumed.
+    pub input: Vec<i64>,
+    pub output: Vec<i64>,
+    pub memory: Vec<(usize, Vec<i64>)>,
+}
+
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("unsupported snapshot version {0}, expected {}", VERSION)]
+    UnsupportedVersion(u32),
+    #[error("line {line}: {reason}")]
+    Malformed { line: usize, reason: String },
+    #[error("missing field '{0}'")]
+    MissingField(&'static str),
+}
+
+impl Snapshot {
+    pub fn write_to(&self, mut w: impl Write) -> io::Result<()> {
+        writeln!(w, "{} {}", HEADER, VERSION)?;
+        writeln!(w, "ip {}", self.ip)?;
+        writeln!(w, "relative-base {}", self.relative_base)?;
+        let status = match self.status {
+            RunState::Paused => "paused".to_owned(),
+            RunState::Halted => "halted".to_owned(),
+            RunState::NeedsInput => "needs-input".to_owned(),
+            RunState::Breakpoint(address) => format!("breakpoint {}", address),
+            RunState::StepLimit => "step-limit".to_owned(),
+            RunState::BudgetExhausted => "budget-exhausted".to_owned(),
+            RunState::TimedOut => "timed-out".to_owned(),
+            RunState::Output(value) => format!("output {}", value),
+            RunState::Faulted => "faulted".to_owned(),
+            // Watchpoints belong to the debugging session rather than the machine
+            RunState::Watchpoint(_) => "paused".to_owned(),
+        };
+        writeln!(w, "status {}", status)?;
+        writeln!(w, "input {}", join(&self.input))?;
+        writeln!(w, "output {}", join(&self.output))?;
+        for (start, cells) in &self.memory {
+            writeln!(w, "memory {} {}", start, join(cells))?;
+        }
+        Ok(())
+    }
+
+    pub fn read_from(r: impl BufRead) -> Result<Self, SnapshotError> {
+        let mut lines = r.lines();
+        let header = lines.next().transpose()?.unwrap_or_default();
+        let version = match header.split_whitespace().collect::<Vec<_>>()[..] {
+            [HEADER, version] => version
+                .parse()
+                .map_err(|_| malformed(1, "invalid version"))?,
+            _ => return Err(malformed(1, "missing snapshot header")),
+        };
+        if version != VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+
+        let mut ip = None;
+        let mut relative_base = None;
+        let mut status = None;
+        let mut input = None;
+        let mut output = None;
+        let mut memory = Vec::new();
+        for (idx, line) in lines.enumerate() {
+            let line_number = idx + 2;
+            let line = line?;
+            let err = |reason: &str| malformed(line_number, reason);
+            let mut fields = line.split_whitespace();
+            let key = match fields.next() {
+                Some(key) => key,
+                None => continue,
+            };
+            let args = fields.collect::<Vec<_>>();
+
+            match (key, &args[..]) {
+                ("ip", [value]) => ip = Some(value.parse().map_err(|_| err("invalid ip"))?),
+                ("relative-base", [value]) => {
+                    relative_base = Some(value.parse().map_err(|_| err("invalid relative base"))?)
+                }
+                ("status", args) => {
+                    status = Some(parse_status(args).ok_or_else(|| err("invalid status"))?)
+                }
+                ("input", args) => {
+                    input = Some(parse_values(args).ok_or_else(|| err("invalid input"))?)
+                }
+                ("output", args) => {
+                    output = Some(parse_values(args).ok_or_else(|| err("invalid output"))?)
+                }
+                ("memory", [start, values @ ..]) => {
+                    let start = start.parse().map_err(|_| err("invalid address"))?;
+                    let values = parse_values(values).ok_or_else(|| err("invalid memory"))?;
+                    memory.push((start, values));
+                }
+                _ => return Err(err(&format!("unexpected '{}'", line))),
+            }
+        }
+
+        let missing = SnapshotError::MissingField;
+        Ok(Self {
+            ip: ip.ok_or_else(|| missing("ip"))?,
+            relative_base: relative_base.ok_or_else(|| missing("relative-base"))?,
+            status: status.ok_or_else(|| missing("status"))?,
+            input: input.ok_or_else(|| missing("input"))?,
+            output: output.ok_or_else(|| missing("output"))?,
+            memory,
+        })
+    }
+}
+
+/// Zero runs at least this long split a memory segment in two.
+const MIN_GAP: usize = 16;
+
+/// Trims zeros from memory segments, splitting them at long runs of zeros so
+/// sparse memory doesn't bloat the snapshot.
+pub(super) fn compact(segments: Vec<(usize, Vec<i64>)>) -> Vec<(usize, Vec<i64>)> {
+    let mut compacted: Vec<(usize, Vec<i64>)> = Vec::new();
+    for (start, cells) in segments {
+        for (idx, &value) in cells.iter().enumerate() {
+            if value == 0 {
+                continue;
+            }
+            let address = start + idx;
+            match compacted.last_mut() {
+                Some((run_start, run)) if address - (*run_start + run.len()) < MIN_GAP => {
+                    run.resize(address - *run_start, 0);
+                    run.push(value);
+                }
+                _ => compacted.push((address, vec![value])),
+            }
+        }
+    }
+    compacted
+}
+
+fn malformed(line: usize, reason: &str) -> SnapshotError {
+    SnapshotError::Malformed {
+        line,
+        reason: reason.to_owned(),
+    }
+}
+
+fn join(values: &[i64]) -> String {
+    values
+        .iter()
+        .map(i64::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn parse_values(args: &[&str]) -> Option<Vec<i64>> {
+    match args {
+        [] => Some(Vec::new()),
+        [values] => values.split(',').map(|v| v.parse().ok()).collect(),
+        _ => None,
+    }
+}
+
+fn parse_status(args: &[&str]) -> Option<RunState> {
+    let state = match args {
+        ["paused"] => RunState::Paused,
+        ["halted"] => RunState::Halted,
+        ["needs-input"] => RunState::NeedsInput,
+        ["breakpoint", address] => RunState::Breakpoint(address.parse().ok()?),
+        ["step-limit"] => RunState::StepLimit,
+        ["budget-exhausted"] => RunState::BudgetExhausted,
+        ["timed-out"] => RunState::TimedOut,
+        ["output", value] => RunState::Output(value.parse().ok()?),
+        ["faulted"] => RunState::Faulted,
+        _ => return None,
+    };
+    Some(state)
+}
+
+#[test]
+fn checkpoint_and_resume() {
+    use crate::{IntcodeComputer, PagedMemory};
+
+    // Sums inputs until it reads a zero, outputting the running total
+    let program = crate::assemble(
+        "
+        loop: in [total+1]
+              jf [total+1], #done
+              add [total], [total+1], [total]
+              out [total]
+              jt #1, #loop
+        done: hlt
+        total: data 0, 0
+        ",
+    )
+    .unwrap();
+
+    let mut computer = IntcodeComputer::new(&program);
+    computer.input(3);
+    computer.input(4);
+    assert_eq!(computer.run_to_output().unwrap(), RunState::Output(3));
+    computer.poke(1_000_000, 42);
+
+    let mut saved = Vec::new();
+    computer.snapshot().write_to(&mut saved).unwrap();
+    let snapshot = Snapshot::read_from(&saved[..]).unwrap();
+    assert_eq!(snapshot, computer.snapshot());
+    assert_eq!(snapshot.memory.len(), 2);
+    assert_eq!(snapshot.memory[1], (1_000_000, vec![42]));
+
+    let mut restored = IntcodeComputer::with_memory(&[], PagedMemory::default());
+    restored.restore(&snapshot);
+    for c in &mut [&mut computer, &mut restored] {
+        c.input(5);
+        c.input(0);
+        assert_eq!(c.run().unwrap(), RunState::Halted);
+        assert_eq!(c.output(), [3, 7, 12]);
+        assert_eq!(c.memory().read(1_000_000), 42);
+    }
+}
+
+#[test]
+fn invalid_snapshots() {
+    let read = |text: &str| {
+        Snapshot::read_from(text.as_bytes())
+            .unwrap_err()
+            .to_string()
+    };
+
+    assert_eq!(
+        read("intcode-snapshot 2\n"),
+        "unsupported snapshot version 2, expected 1"
+    );
+    assert_eq!(read("1,2,3\n"), "line 1: missing snapshot header");
+    assert_eq!(
+        read("intcode-snapshot 1\nip 0\nstatus sleeping\n"),
+        "line 3: invalid status"
+    );
+    assert_eq!(
+        read("intcode-snapshot 1\nip 0\nrelative-base 0\nstatus halted\ninput\n"),
+        "missing field 'output'"
+    );
+}
@@ -0,0 +1,130 @@
+use super::Instruction;
+use std::io::{self, Write};
+
+/// A single executed instruction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub ip: usize,
+    pub relative_base: i64,
+    pub instruction: Instruction,
+    /// Values of the source operands the instruction read, in order.
+    pub reads: Vec<i64>,
+    /// The address written to and the value stored there, if any.
+    pub write: Option<(usize, i64)>,
+}
+
+/// A record of every instruction executed while tracing was enabled.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Trace {
+    entries: Vec<TraceEntry>,
+}
+
+impl Trace {
+    pub fn entries(&self) -> &[TraceEntry] {
+        &self.entries
+    }
+
+    pub(super) fn push(&mut self, entry: TraceEntry) {
+        self.entries.push(entry)
+    }
+
+    /// Writes one line per instruction, suitable for diffing two runs.
+    pub fn write_text(&self, mut w: impl Write) -> io::Result<()> {
+        for (step, entry) in self.entries.iter().enumerate() {
+            let reads = entry.reads.iter().map(i64::to_string).collect::<Vec<_>>();
+            write!(
+                w,
+                "{:>8} {:05} rb={:<6} {:<32} reads=[{}]",
+                step,
+                entry.ip,
+                entry.relative_base,
+                entry.instruction.to_string(),
+                reads.join(", ")
+            )?;
+            if let Some((address, value)) = entry.write {
+                write!(w, " [{}] <- {}", address, value)?;
+            }
+            writeln!(w)?;
+        }
+        Ok(())
+    }
+
+    /// Writes one JSON object per instruction.
+    pub fn write_json_lines(&self, mut w: impl Write) -> io::Result<()> {
+        for (step, entry) in self.entries.iter().enumerate() {
+            let reads = entry.reads.iter().map(i64::to_string).collect::<Vec<_>>();
+            // Instruction text never contains characters which need escaping
+            write!(
+                w,
+                r#"{{"step":{},"ip":{},"relative_base":{},"opcode":"{}","instruction":"{}","reads":[{}],"write":"#,
+                step,
+                entry.ip,
+                entry.relative_base,
+                entry.instruction.opcode().mnemonic(),
+                entry.instruction,
+                reads.join(",")
+            )?;
+            match entry.write {
+                Some((address, value)) => {
+                    writeln!(w, r#"{{"address":{},"value":{}}}}}"#, address, value)?
+                }
+                None => writeln!(w, "null}}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn trace_quine() {
+    use crate::IntcodeComputer;
+
+    let program = [
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+    let mut computer = IntcodeComputer::new(&program);
+    computer.start_trace();
+    computer.run().unwrap();
+    let trace = computer.take_trace().unwrap();
+
+    // Five instructions per loop, sixteen loops and the final halt
+    assert_eq!(trace.entries().len(), 5 * 16 + 1);
+    assert_eq!(
+        trace.entries()[2],
+        TraceEntry {
+            ip: 4,
+            relative_base: 1,
+            instruction: Instruction::Add(
+                super::Operand::Position(100),
+                super::Operand::Immediate(1),
+                super::Operand::Position(100),
+            ),
+            reads: vec![0, 1],
+            write: Some((100, 1)),
+        }
+    );
+
+    let mut text = Vec::new();
+    trace.write_text(&mut text).unwrap();
+    let text = String::from_utf8(text).unwrap();
+    assert_eq!(
+        text.lines().nth(1).unwrap(),
+        "       1 00002 rb=1      out rb-1                         reads=[109]"
+    );
+
+    let mut json = Vec::new();
+    trace.write_json_lines(&mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    assert_eq!(
+        json.lines().nth(2).unwrap(),
+        r#"{"step":2,"ip":4,"relative_base":1,"opcode":"add","instruction":"add [100], #1, [100]","reads":[0,1],"write":{"address":100,"value":1}}"#
+    );
+    assert!(json.lines().last().unwrap().ends_with(r#""write":null}"#));
+
+    // The target of a jump which isn't taken is never read
+    let mut computer = IntcodeComputer::new(&[106, 1, -1, 104, 7, 99]);
+    computer.start_trace();
+    computer.run().unwrap();
+    assert_eq!(computer.output(), [7]);
+    assert_eq!(computer.trace().unwrap().entries()[0].reads, [1]);
+}
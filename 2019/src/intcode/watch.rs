@@ -0,0 +1,137 @@
+use super::Instruction;
+use std::ops::Range;
+
+/// The kind of memory access a watchpoint triggers on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+impl Access {
+    fn includes(self, access: Access) -> bool {
+        self == Access::ReadWrite || self == access
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Watchpoint {
+    pub addresses: Range<usize>,
+    pub access: Access,
+}
+
+/// Details of the access which triggered a watchpoint.
+///
+/// Only operand accesses are watched, not the fetching of instructions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WatchHit {
+    /// Address of the instruction which made the access.
+    pub ip: usize,
+    pub instruction: Instruction,
+    pub address: usize,
+    /// Either `Access::Read` or `Access::Write`.
+    pub access: Access,
+    /// The value held before the instruction executed.
+    pub old: i64,
+    /// The value written, or the same as `old` for reads.
+    pub new: i64,
+}
+
+/// Memory an instruction is about to access, captured before it executes.
+pub(super) struct Accesses {
+    pub ip: usize,
+    /// Each source operand read, with its address unless it's immediate,
+    /// along with its value.
+    pub reads: Vec<(Option<usize>, i64)>,
+    /// The address written to along with the value it held beforehand.
+    pub write: Option<(usize, i64)>,
+}
+
+impl Accesses {
+    /// Finds the first watchpoint triggered by these accesses, checking the
+    /// write before any reads. `read` gives the value at an address after
+    /// the instruction has executed.
+    pub fn check(
+        &self,
+        watchpoints: &[Watchpoint],
+        instruction: Instruction,
+        read: impl Fn(usize) -> i64,
+    ) -> Option<WatchHit> {
+        let writes = self.write.iter().map(|&(a, v)| (a, v, Access::Write));
+        let reads = self
+            .reads
+            .iter()
+            .filter_map(|&(a, v)| Some((a?, v, Access::Read)));
+        writes.chain(reads).find_map(|(address, old, access)| {
+            watchpoints
+                .iter()
+                .find(|w| w.access.includes(access) && w.addresses.contains(&address))
+                .map(|_| WatchHit {
+                    ip: self.ip,
+                    instruction,
+                    address,
+                    access,
+                    old,
+                    new: match access {
+                        Access::Write => read(address),
+                        _ => old,
+                    },
+                })
+        })
+    }
+}
+
+#[test]
+fn watch_self_modifying() {
+    use crate::{IntcodeComputer, RunState};
+
+    // Patches the immediate operand of its own output instruction
+    let program = crate::assemble(
+        "
+              add [patch+1], #5, [patch+1]
+              add [scratch], [scratch], [scratch]
+        patch: out #1
+              hlt
+        scratch: data 3
+        ",
+    )
+    .unwrap();
+
+    let mut computer = IntcodeComputer::new(&program);
+    computer.watch(9..10, Access::Write);
+    computer.watch(11..12, Access::Read);
+    let hit = match computer.run().unwrap() {
+        RunState::Watchpoint(hit) => hit,
+        state => panic!("unexpected state {:?}", state),
+    };
+    assert_eq!(
+        (hit.ip, hit.address, hit.access, hit.old, hit.new),
+        (0, 9, Access::Write, 1, 6)
+    );
+    assert_eq!(computer.ip(), 4);
+
+    // Only reads of the scratch cell are watched, not its write
+    match computer.run().unwrap() {
+        RunState::Watchpoint(hit) => assert_eq!(
+            (hit.ip, hit.address, hit.access, hit.old, hit.new),
+            (4, 11, Access::Read, 3, 3)
+        ),
+        state => panic!("unexpected state {:?}", state),
+    }
+
+    assert!(computer.unwatch(11..12));
+    assert!(!computer.unwatch(11..12));
+    assert_eq!(computer.run().unwrap(), RunState::Halted);
+    assert_eq!(computer.output(), [6]);
+
+    // Watching doesn't make a jump which falls through read its target
+    let program = [1106, 1, 5, 104, 7, 99];
+    let mut computer = IntcodeComputer::new(&program);
+    computer.watch(0..6, Access::Read);
+    assert_eq!(computer.run().unwrap(), RunState::Halted);
+    assert_eq!(computer.output(), [7]);
+    let mut computer = IntcodeComputer::new(&[106, 1, -1, 104, 7, 99]);
+    computer.watch(0..1, Access::Write);
+    assert_eq!(computer.run().unwrap(), RunState::Halted);
+}
@@ -1,3 +1,15 @@
+mod asm;
 mod intcode;
+mod network;
 
-pub use intcode::{IntcodeComputer, IntcodeError};
+pub use asm::{assemble, assemble_with, AssembleError, AssembleErrorKind};
+pub use network::{Network, NetworkResult, Termination};
+
+pub use intcode::{
+    decode_ascii, disassemble, disassemble_with, translate, Access, AotMachine, Arithmetic,
+    AsciiConsole, AsciiOutput, BasicBlock, Builder, ControlFlowGraph, CowMemory, CustomOpcode,
+    DenseMemory, Disassembled, Disassembly, Engine, FaultContext, Handler, InputSource,
+    Instruction, IntcodeComputer, IntcodeError, Item, IterInput, Memory, Opcode, Operand, Operands,
+    OutputSink, PagedMemory, Pipe, Profile, Program, ProgramError, RunState, Snapshot,
+    SnapshotError, Successor, Trace, TraceEntry, WatchHit, Watchpoint,
+};
@@ -0,0 +1,466 @@
+use crate::{IntcodeComputer, IntcodeError, OutputSink, RunState};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Termination {
+    /// Every machine halted.
+    Halted,
+    /// The remaining machines are all waiting for input which will never arrive.
+    Idle,
+    /// At least one machine faulted, and the rest halted or went idle.
+    Faulted,
+}
+
+pub struct NetworkResult {
+    pub termination: Termination,
+    /// Everything each machine output, in order.
+    pub outputs: Vec<Vec<i64>>,
+    /// Packets addressed to machines outside of the network.
+    pub unrouted: Vec<Vec<i64>>,
+    pub faults: Vec<(usize, IntcodeError)>,
+    pub machines: Vec<IntcodeComputer>,
+}
+
+enum Routing {
+    /// Every output is sent to each of the listed machines.
+    Direct(Vec<Vec<usize>>),
+    /// Outputs are grouped into packets whose first value is the address of
+    /// the machine the remaining values are delivered to.
+    Addressed { packet_size: usize },
+}
+
+/// A set of `IntcodeComputer`s running on their own threads, with the output
+/// of each machine routed to the input of others over channels.
+///
+/// Values already queued with `IntcodeComputer::input` are consumed before
+/// anything received from the network.
+pub struct Network {
+    machines: Vec<IntcodeComputer>,
+    routing: Routing,
+    idle_input: Option<i64>,
+}
+
+impl Network {
+    /// A network with no links, whose machines' outputs are only collected.
+    pub fn new(machines: Vec<IntcodeComputer>) -> Self {
+        let links = vec![Vec::new(); machines.len()];
+        Self {
+            machines,
+            routing: Routing::Direct(links),
+            idle_input: None,
+        }
+    }
+
+    /// Links each machine to the next one.
+    pub fn pipeline(machines: Vec<IntcodeComputer>) -> Self {
+        let n = machines.len();
+        (1..n).fold(Self::new(machines), |network, i| network.connect(i - 1, i))
+    }
+
+    /// Links each machine to the next one and the last back to the first.
+    pub fn ring(machines: Vec<IntcodeComputer>) -> Self {
+        let n = machines.len();
+        (0..n).fold(Self::new(machines), |network, i| {
+            network.connect(i, (i + 1) % n)
+        })
+    }
+
+    /// Routes addressed packets of `packet_size` values between all machines.
+    pub fn mesh(machines: Vec<IntcodeComputer>, packet_size: usize) -> Self {
+        assert!(packet_size > 1, "packets need an address and a payload");
+        Self {
+            machines,
+            routing: Routing::Addressed { packet_size },
+            idle_input: None,
+        }
+    }
+
+    /// Sends the output of machine `from` to machine `to`.
+    ///
+    /// Panics if the network routes addressed packets.
+    pub fn connect(mut self, from: usize, to: usize) -> Self {
+        assert!(from < self.machines.len(), "no machine {}", from);
+        assert!(to < self.machines.len(), "no machine {}", to);
+        match &mut self.routing {
+            Routing::Direct(links) => links[from].push(to),
+            Routing::Addressed { .. } => panic!("can't link machines in a mesh"),
+        }
+        self
+    }
+
+    /// Instead of blocking, machines waiting on an empty inbox are given
+    /// `value` as input. A machine counts as idle once it has been given
+    /// `value` twice without producing output in between.
+    pub fn idle_input(mut self, value: i64) -> Self {
+        self.idle_input = Some(value);
+        self
+    }
+
+    pub fn run(self) -> NetworkResult {
+        let Network {
+            machines,
+            routing,
+            idle_input,
+        } = self;
+        let n = machines.len();
+        let shared = Arc::new(Shared {
+            waiting: AtomicUsize::new(0),
+            halted: (0..n).map(|_| AtomicBool::new(false)).collect(),
+            pending: (0..n).map(|_| AtomicUsize::new(0)).collect(),
+            activity: (0..n).map(|_| AtomicUsize::new(0)).collect(),
+            done: AtomicBool::new(false),
+        });
+        let (inboxes, receivers): (Vec<_>, Vec<_>) = (0..n).map(|_| mpsc::channel()).unzip();
+        let (collector, collected) = mpsc::channel();
+
+        let handles = machines
+            .into_iter()
+            .zip(receivers)
+            .enumerate()
+            .map(|(id, (mut computer, inbox))| {
+                let (targets, packet_size) = match &routing {
+                    Routing::Direct(links) => (links[id].clone(), None),
+                    Routing::Addressed { packet_size } => (Vec::new(), Some(*packet_size)),
+                };
+                computer.attach_output(Router {
+                    id,
+                    targets,
+                    packet_size,
+                    packet: Vec::new(),
+                    inboxes: inboxes.clone(),
+                    collector: collector.clone(),
+                    shared: shared.clone(),
+                });
+
+                let node = Node {
+                    id,
+                    inbox,
+                    shared: shared.clone(),
+                    idle_input,
+                };
+                thread::spawn(move || node.run(computer))
+            })
+            .collect::<Vec<_>>();
+        drop(collector);
+
+        let mut machines = Vec::with_capacity(n);
+        let mut faults = Vec::new();
+        for handle in handles {
+            let (computer, fault) = handle.join().expect("network thread panicked");
+            if let Some(fault) = fault {
+                faults.push((machines.len(), fault));
+            }
+            machines.push(computer);
+        }
+
+        let mut outputs = vec![Vec::new(); n];
+        let mut unrouted = Vec::new();
+        for event in collected {
+            match event {
+                Event::Output(id, value) => outputs[id].push(value),
+                Event::Unrouted(packet) => unrouted.push(packet),
+            }
+        }
+
+        let termination = if !faults.is_empty() {
+            Termination::Faulted
+        } else if shared.halted.iter().all(|h| h.load(Ordering::SeqCst)) {
+            Termination::Halted
+        } else {
+            Termination::Idle
+        };
+
+        NetworkResult {
+            termination,
+            outputs,
+            unrouted,
+            faults,
+            machines,
+        }
+    }
+}
+
+struct Shared {
+    // Machines which are halted or waiting for input
+    waiting: AtomicUsize,
+    // Machines which halted or faulted, and so won't run again
+    halted: Vec<AtomicBool>,
+    // Messages sent to each machine which it hasn't received yet
+    pending: Vec<AtomicUsize>,
+    // Number of values output by each machine
+    activity: Vec<AtomicUsize>,
+    done: AtomicBool,
+}
+
+impl Shared {
+    /// True once every machine is halted or waiting and no live machine has
+    /// a message on its way. Machines only become busy again by receiving a
+    /// message, so this state is final.
+    fn is_idle(&self) -> bool {
+        self.waiting.load(Ordering::SeqCst) == self.halted.len()
+            && self
+                .pending
+                .iter()
+                .zip(&self.halted)
+                .all(|(p, h)| h.load(Ordering::SeqCst) || p.load(Ordering::SeqCst) == 0)
+    }
+
+    fn check_idle(&self) -> bool {
+        if self.is_idle() {
+            self.done.store(true, Ordering::SeqCst);
+        }
+        self.done.load(Ordering::SeqCst)
+    }
+}
+
+enum Event {
+    Output(usize, i64),
+    Unrouted(Vec<i64>),
+}
+
+struct Router {
+    id: usize,
+    targets: Vec<usize>,
+    packet_size: Option<usize>,
+    packet: Vec<i64>,
+    inboxes: Vec<Sender<Vec<i64>>>,
+    collector: Sender<Event>,
+    shared: Arc<Shared>,
+}
+
+impl Router {
+    fn send(&self, target: usize, message: Vec<i64>) {
+        // Count the message before sending it so the receiver never observes
+        // an uncounted message.
+        self.shared.pending[target].fetch_add(1, Ordering::SeqCst);
+        if self.inboxes[target].send(message).is_err() {
+            self.shared.pending[target].fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+}
+
+impl OutputSink for Router {
+    fn emit(&mut self, value: i64) {
+        self.shared.activity[self.id].fetch_add(1, Ordering::SeqCst);
+        let _ = self.collector.send(Event::Output(self.id, value));
+
+        for &target in &self.targets {
+            self.send(target, vec![value]);
+        }
+
+        if let Some(packet_size) = self.packet_size {
+            self.packet.push(value);
+            if self.packet.len() == packet_size {
+                let mut packet = std::mem::take(&mut self.packet);
+                let address = packet[0];
+                if address >= 0 && (address as usize) < self.inboxes.len() {
+                    packet.remove(0);
+                    self.send(address as usize, packet);
+                } else {
+                    let _ = self.collector.send(Event::Unrouted(packet));
+                }
+            }
+        }
+    }
+}
+
+struct Node {
+    id: usize,
+    inbox: Receiver<Vec<i64>>,
+    shared: Arc<Shared>,
+    idle_input: Option<i64>,
+}
+
+impl Node {
+    fn run(self, mut computer: IntcodeComputer) -> (IntcodeComputer, Option<IntcodeError>) {
+        let mut idle_polls = 0;
+        let mut last_activity = 0;
+        let (halted, fault) = loop {
+            match computer.run() {
+                Ok(RunState::NeedsInput) => {}
+                Ok(RunState::Halted) => break (true, None),
+                Ok(_) => continue,
+                Err(e) => break (true, Some(e)),
+            }
+
+            let activity = self.shared.activity[self.id].load(Ordering::SeqCst);
+            if activity != last_activity {
+                last_activity = activity;
+                idle_polls = 0;
+            }
+
+            let message = match self.inbox.try_recv() {
+                Ok(message) => message,
+                Err(_) => match self.idle_input {
+                    // Keep feeding idle input until the machine looks idle,
+                    // after which more of it wouldn't change anything.
+                    Some(idle) if idle_polls < 2 => {
+                        idle_polls += 1;
+                        computer.input(idle);
+                        continue;
+                    }
+                    _ => match self.wait() {
+                        Some(message) => message,
+                        None => break (false, None),
+                    },
+                },
+            };
+
+            idle_polls = 0;
+            for value in message {
+                computer.input(value);
+            }
+            self.shared.pending[self.id].fetch_sub(1, Ordering::SeqCst);
+        };
+
+        if halted {
+            self.shared.halted[self.id].store(true, Ordering::SeqCst);
+            self.shared.waiting.fetch_add(1, Ordering::SeqCst);
+        }
+        // Drop the router so the collector sees the end of the output
+        computer.detach_output();
+        (computer, fault)
+    }
+
+    /// Blocks until a message arrives, or returns `None` once the whole
+    /// network is idle.
+    fn wait(&self) -> Option<Vec<i64>> {
+        self.shared.waiting.fetch_add(1, Ordering::SeqCst);
+        loop {
+            match self.inbox.recv_timeout(POLL_INTERVAL) {
+                Ok(message) => {
+                    self.shared.waiting.fetch_sub(1, Ordering::SeqCst);
+                    return Some(message);
+                }
+                Err(_) => {
+                    if self.shared.check_idle() {
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn day07_feedback_loop() {
+    use itertools::Itertools;
+
+    let program = include_str!("../input/day07.txt")
+        .parse::<crate::Program>()
+        .unwrap();
+
+    let max = (5..=9)
+        .permutations(5)
+        .map(|phases| {
+            let machines = phases
+                .iter()
+                .map(|&phase| {
+                    let mut computer = IntcodeComputer::new(&program);
+                    computer.input(phase);
+                    computer
+                })
+                .collect::<Vec<_>>();
+            let mut network = Network::ring(machines);
+            network.machines[0].input(0);
+
+            let result = network.run();
+            assert_eq!(result.termination, Termination::Halted);
+            *result.outputs[4].last().unwrap()
+        })
+        .max()
+        .unwrap();
+
+    assert_eq!(max, 44282086);
+}
+
+#[test]
+fn pipeline_and_deadlock() {
+    let double = crate::assemble(
+        "
+        loop: in [100]
+              mul [100], #2, [100]
+              out [100]
+              jt #1, #loop
+        ",
+    )
+    .unwrap();
+
+    let machines = vec![IntcodeComputer::new(&double); 3];
+    let mut network = Network::pipeline(machines);
+    network.machines[0].input(1);
+    network.machines[0].input(5);
+
+    let result = network.run();
+    assert_eq!(result.termination, Termination::Idle);
+    assert_eq!(result.outputs, [vec![2, 10], vec![4, 20], vec![8, 40]]);
+    assert!(result.faults.is_empty());
+}
+
+#[test]
+fn mesh() {
+    // Reads its address, then increments a counter and forwards it to the
+    // next machine until it reaches 10, at which point it's sent to address
+    // 255 and the machine halts.
+    let node = crate::assemble(
+        "
+              in [addr]
+              add [addr], #1, [next]
+              eq [next], #3, [wrap]
+              jf [wrap], #start
+              add #0, #0, [next]
+        start: jt [addr], #recv
+              out [next]
+              out #1
+        recv: in [value]
+              jf [value], #recv  ; idle input
+              add [value], #1, [value]
+              lt [value], #10, [wrap]
+              jt [wrap], #send
+              out #255
+              out [value]
+              hlt
+        send: out [next]
+              out [value]
+              jt #1, #recv
+        addr:  data 0
+        next:  data 0
+        wrap:  data 0
+        value: data 0
+        ",
+    )
+    .unwrap();
+
+    let machines = (0..3)
+        .map(|address| {
+            let mut computer = IntcodeComputer::new(&node);
+            computer.input(address);
+            computer
+        })
+        .collect();
+    let result = Network::mesh(machines, 2).idle_input(0).run();
+
+    assert_eq!(result.unrouted, [vec![255, 10]]);
+    assert_eq!(result.termination, Termination::Idle);
+    assert_eq!(result.outputs[0], [1, 1, 1, 4, 1, 7, 255, 10]);
+}
+
+#[test]
+fn faulted() {
+    let echo = crate::assemble("in [100]\nout [100]\nhlt").unwrap();
+    let machines = vec![IntcodeComputer::new(&echo), IntcodeComputer::new(&[0])];
+    let mut network = Network::pipeline(machines);
+    network.machines[0].input(1);
+
+    let result = network.run();
+    assert_eq!(result.termination, Termination::Faulted);
+    assert_eq!(result.outputs, [vec![1], vec![]]);
+    assert_eq!(result.faults.len(), 1);
+    assert_eq!(result.faults[0].0, 1);
+}
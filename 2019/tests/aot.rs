@@ -0,0 +1,165 @@
+use advent_of_code_2019::{translate, IntcodeComputer, IntcodeError, Pipe, Program, RunState};
+use itertools::Itertools;
+use std::time::Duration;
+
+// Regenerate with `cargo run --bin intcode-aot input/dayNN.txt > tests/aot/dayNN.rs`
+#[rustfmt::skip]
+#[path = "aot/day05.rs"]
+mod day05;
+#[rustfmt::skip]
+#[path = "aot/day07.rs"]
+mod day07;
+#[rustfmt::skip]
+#[path = "aot/day09.rs"]
+mod day09;
+
+type Run = fn(&mut IntcodeComputer) -> Result<RunState, IntcodeError>;
+
+fn parse(source: &str) -> Program {
+    source.parse().unwrap()
+}
+
+/// Runs `program` to completion with both the interpreter and `run`,
+/// checking that they finish in the same state.
+fn compare(program: &[i64], input: &[i64], run: Run) -> Vec<i64> {
+    let mut interpreted = IntcodeComputer::new(program);
+    let mut translated = IntcodeComputer::new(program);
+    for &value in input {
+        interpreted.input(value);
+        translated.input(value);
+    }
+
+    assert_eq!(interpreted.run(), Ok(RunState::Halted));
+    assert_eq!(run(&mut translated), Ok(RunState::Halted));
+    assert_eq!(interpreted.output(), translated.output());
+    assert_eq!(interpreted.ip(), translated.ip());
+    assert_eq!(interpreted.relative_base(), translated.relative_base());
+    let extent = interpreted.memory().extent();
+    assert_eq!(
+        interpreted.memory().read_range(0, extent),
+        translated.memory().read_range(0, extent)
+    );
+    translated.output().to_vec()
+}
+
+#[test]
+fn fixtures_up_to_date() {
+    let fixtures = [
+        (
+            include_str!("../input/day05.txt"),
+            include_str!("aot/day05.rs"),
+        ),
+        (
+            include_str!("../input/day07.txt"),
+            include_str!("aot/day07.rs"),
+        ),
+        (
+            include_str!("../input/day09.txt"),
+            include_str!("aot/day09.rs"),
+        ),
+    ];
+    for (input, generated) in fixtures.iter() {
+        assert!(
+            translate(&parse(input)) == *generated,
+            "fixture out of date"
+        );
+    }
+}
+
+#[test]
+fn day05() {
+    let program = parse(include_str!("../input/day05.txt"));
+    assert_eq!(compare(&program, &[1], day05::run).last(), Some(&13933662));
+    assert_eq!(compare(&program, &[5], day05::run), [2369720]);
+}
+
+#[test]
+fn day07_amplifiers() {
+    let program = parse(include_str!("../input/day07.txt"));
+    let best = (0..5)
+        .permutations(5)
+        .map(|phases| {
+            phases.iter().fold(0, |signal, &phase| {
+                compare(&program, &[phase, signal], day07::run)[0]
+            })
+        })
+        .max();
+    assert_eq!(best, Some(34852));
+}
+
+#[test]
+fn day07_feedback_loop() {
+    let program = parse(include_str!("../input/day07.txt"));
+
+    // Each amplifier repeatedly blocks on input, so translated code must
+    // resume correctly
+    let feedback = |phases: &[i64], run: Run| {
+        let pipes = (0..5).map(|_| Pipe::new()).collect::<Vec<_>>();
+        let mut amplifiers = phases
+            .iter()
+            .enumerate()
+            .map(|(idx, &phase)| {
+                let mut computer = IntcodeComputer::new(&program);
+                computer.input(phase);
+                computer.attach_input(pipes[idx].clone());
+                computer.attach_output(pipes[(idx + 1) % 5].clone());
+                computer
+            })
+            .collect::<Vec<_>>();
+        pipes[0].push(0);
+        while amplifiers.iter().any(|c| c.status() != RunState::Halted) {
+            for computer in &mut amplifiers {
+                run(computer).unwrap();
+            }
+        }
+        pipes[0].pop().unwrap()
+    };
+
+    let best = (5..10)
+        .permutations(5)
+        .map(|phases| {
+            let translated = feedback(&phases, day07::run);
+            assert_eq!(translated, feedback(&phases, IntcodeComputer::run));
+            translated
+        })
+        .max();
+    assert_eq!(best, Some(44282086));
+}
+
+#[test]
+fn day09() {
+    let program = parse(include_str!("../input/day09.txt"));
+    assert_eq!(compare(&program, &[1], day09::run), [2406950601]);
+    assert_eq!(compare(&program, &[2], day09::run), [83239]);
+}
+
+#[test]
+fn limits() {
+    let program = parse(include_str!("../input/day09.txt"));
+    let mut interpreted = IntcodeComputer::new(&program);
+    let mut translated = IntcodeComputer::new(&program);
+    for computer in [&mut interpreted, &mut translated].iter_mut() {
+        computer.input(2);
+        computer.set_budget(Some(10_000));
+    }
+
+    // Both stop after exactly the same instructions on every run
+    let mut runs = 0;
+    loop {
+        let state = interpreted.run();
+        assert_eq!(day09::run(&mut translated), state);
+        assert_eq!(interpreted.ip(), translated.ip());
+        assert_eq!(interpreted.relative_base(), translated.relative_base());
+        runs += 1;
+        if state != Ok(RunState::BudgetExhausted) {
+            break;
+        }
+    }
+    assert!(runs > 10);
+    assert_eq!(translated.output(), [83239]);
+
+    let mut computer = IntcodeComputer::new(&program);
+    computer.set_timeout(Some(Duration::from_secs(0)));
+    computer.input(2);
+    assert_eq!(day09::run(&mut computer), Ok(RunState::TimedOut));
+}
@@ -0,0 +1,1046 @@
+// Generated by intcode-aot, do not edit.
+#![allow(clippy::all, unused_parens)]
+
+use advent_of_code_2019::{AotMachine, IntcodeComputer, IntcodeError, RunState};
+
+static CODE: &[(usize, &[i64])] = &[
+    (0, &[
+        3, 225, 1, 225, 6, 6,
+    ]),
+    (7, &[
+        1, 238, 225, 104,
+    ]),
+    (12, &[
+        1101, 37, 61, 225, 101, 34, 121, 224, 1001, 224, -49, 224, 4, 224, 102, 8,
+        223, 223, 1001, 224, 6, 224, 1, 224, 223, 223, 1101, 67, 29, 225, 1, 14,
+        65, 224, 101, -124, 224, 224, 4, 224, 1002, 223, 8, 223, 101, 5, 224, 224,
+        1, 224, 223, 223, 1102, 63, 20, 225, 1102, 27, 15, 225, 1102, 18, 79, 224,
+        101, -1422, 224, 224, 4, 224, 102, 8, 223, 223, 1001, 224, 1, 224, 1, 223,
+        224, 223, 1102, 20, 44, 225, 1001, 69, 5, 224, 101, -32, 224, 224, 4, 224,
+        1002, 223, 8, 223, 101, 1, 224, 224, 1, 223, 224, 223, 1102, 15, 10, 225,
+        1101, 6, 70, 225, 102, 86, 40, 224, 101, -2494, 224, 224, 4, 224, 1002, 223,
+        8, 223, 101, 6, 224, 224, 1, 223, 224, 223, 1102, 25, 15, 225, 1101, 40,
+        67, 224, 1001, 224, -107, 224, 4, 224, 102, 8, 223, 223, 101, 1, 224, 224,
+        1, 223, 224, 223, 2, 126, 95, 224, 101, -1400, 224, 224, 4, 224, 1002, 223,
+        8, 223, 1001, 224, 3, 224, 1, 223, 224, 223, 1002, 151, 84, 224, 101, -2100,
+        224, 224, 4, 224, 102, 8, 223, 223, 101, 6, 224, 224, 1, 224, 223, 223,
+        4, 223, 99,
+    ]),
+    (238, &[
+        1105, 0, 99999, 1105, 227, 247, 1105, 1, 99999, 1005, 227, 99999, 1005, 0, 256, 1105,
+        1, 99999, 1106, 227, 99999, 1106, 0, 265, 1105, 1, 99999, 1006, 0, 99999, 1006, 227,
+        274, 1105, 1, 99999, 1105, 1, 280, 1105, 1, 99999, 1, 225, 225, 225, 1101, 294,
+        0, 0, 105, 1, 0, 1105, 1, 99999, 1106, 0, 300, 1105, 1, 99999, 1, 225,
+        225, 225, 1101, 314, 0, 0, 106, 0, 0, 1105, 1, 99999, 108, 677, 677, 224,
+        1002, 223, 2, 223, 1006, 224, 329, 101, 1, 223, 223, 1107, 677, 226, 224, 102,
+        2, 223, 223, 1006, 224, 344, 101, 1, 223, 223, 8, 677, 677, 224, 1002, 223,
+        2, 223, 1006, 224, 359, 101, 1, 223, 223, 1008, 677, 677, 224, 1002, 223, 2,
+        223, 1006, 224, 374, 101, 1, 223, 223, 7, 226, 677, 224, 1002, 223, 2, 223,
+        1006, 224, 389, 1001, 223, 1, 223, 1007, 677, 677, 224, 1002, 223, 2, 223, 1006,
+        224, 404, 1001, 223, 1, 223, 7, 677, 677, 224, 1002, 223, 2, 223, 1006, 224,
+        419, 1001, 223, 1, 223, 1008, 677, 226, 224, 1002, 223, 2, 223, 1005, 224, 434,
+        1001, 223, 1, 223, 1107, 226, 677, 224, 102, 2, 223, 223, 1005, 224, 449, 1001,
+        223, 1, 223, 1008, 226, 226, 224, 1002, 223, 2, 223, 1006, 224, 464, 1001, 223,
+        1, 223, 1108, 677, 677, 224, 102, 2, 223, 223, 1006, 224, 479, 101, 1, 223,
+        223, 1108, 226, 677, 224, 1002, 223, 2, 223, 1006, 224, 494, 1001, 223, 1, 223,
+        107, 226, 226, 224, 1002, 223, 2, 223, 1006, 224, 509, 1001, 223, 1, 223, 8,
+        226, 677, 224, 102, 2, 223, 223, 1006, 224, 524, 1001, 223, 1, 223, 1007, 226,
+        226, 224, 1002, 223, 2, 223, 1006, 224, 539, 1001, 223, 1, 223, 107, 677, 677,
+        224, 1002, 223, 2, 223, 1006, 224, 554, 1001, 223, 1, 223, 1107, 226, 226, 224,
+        102, 2, 223, 223, 1005, 224, 569, 101, 1, 223, 223, 1108, 677, 226, 224, 1002,
+        223, 2, 223, 1006, 224, 584, 1001, 223, 1, 223, 1007, 677, 226, 224, 1002, 223,
+        2, 223, 1005, 224, 599, 101, 1, 223, 223, 107, 226, 677, 224, 102, 2, 223,
+        223, 1005, 224, 614, 1001, 223, 1, 223, 108, 226, 226, 224, 1002, 223, 2, 223,
+        1005, 224, 629, 101, 1, 223, 223, 7, 677, 226, 224, 102, 2, 223, 223, 1005,
+        224, 644, 101, 1, 223, 223, 8, 677, 226, 224, 102, 2, 223, 223, 1006, 224,
+        659, 1001, 223, 1, 223, 108, 677, 226, 224, 102, 2, 223, 223, 1005, 224, 674,
+        1001, 223, 1, 223, 4, 223, 99,
+    ]),
+];
+
+static BLOCKS: &[(usize, usize, usize)] = &[
+    (0, 6, 2), (7, 11, 1), (12, 223, 58), (238, 241, 1), (241, 244, 1), (244, 247, 1), (247, 250, 1), (250, 253, 1),
+    (253, 256, 1), (256, 259, 1), (259, 262, 1), (262, 265, 1), (265, 268, 1), (268, 271, 1), (271, 274, 1), (274, 277, 1),
+    (277, 280, 1), (280, 288, 3), (291, 294, 1), (294, 297, 1), (297, 300, 1), (300, 308, 3), (311, 314, 1), (314, 325, 3),
+    (325, 329, 1), (329, 340, 3), (340, 344, 1), (344, 355, 3), (355, 359, 1), (359, 370, 3), (370, 374, 1), (374, 385, 3),
+    (385, 389, 1), (389, 400, 3), (400, 404, 1), (404, 415, 3), (415, 419, 1), (419, 430, 3), (430, 434, 1), (434, 445, 3),
+    (445, 449, 1), (449, 460, 3), (460, 464, 1), (464, 475, 3), (475, 479, 1), (479, 490, 3), (490, 494, 1), (494, 505, 3),
+    (505, 509, 1), (509, 520, 3), (520, 524, 1), (524, 535, 3), (535, 539, 1), (539, 550, 3), (550, 554, 1), (554, 565, 3),
+    (565, 569, 1), (569, 580, 3), (580, 584, 1), (584, 595, 3), (595, 599, 1), (599, 610, 3), (610, 614, 1), (614, 625, 3),
+    (625, 629, 1), (629, 640, 3), (640, 644, 1), (644, 655, 3), (655, 659, 1), (659, 670, 3), (670, 674, 1), (674, 677, 2),
+];
+
+pub fn run(computer: &mut IntcodeComputer) -> Result<RunState, IntcodeError> {
+    let mut m = AotMachine::new(computer, CODE, BLOCKS);
+    let result = execute(&mut m);
+    m.finish(result)
+}
+
+fn execute(m: &mut AotMachine) -> Result<RunState, IntcodeError> {
+    loop {
+        match m.block() {
+            Some(0) => {
+                m.set_ip(0);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(225, v);
+                m.set_ip(2);
+                m.retire();
+                let v = m.add(m.load(225), m.load(6))?;
+                m.store(6, v);
+                m.set_ip(6);
+            }
+            Some(7) => {
+                m.set_ip(7);
+                m.retire();
+                let v = m.add(m.load(238), m.load(225))?;
+                m.patch(104, v);
+                m.set_ip(11);
+                continue;
+            }
+            Some(12) => {
+                m.set_ip(12);
+                m.retire();
+                let v = m.add(37, 61)?;
+                m.store(225, v);
+                m.set_ip(16);
+                m.retire();
+                let v = m.add(34, m.load(121))?;
+                m.store(224, v);
+                m.set_ip(20);
+                m.retire();
+                let v = m.add(m.load(224), -49)?;
+                m.store(224, v);
+                m.set_ip(24);
+                m.retire();
+                m.output(m.load(224));
+                m.set_ip(26);
+                m.retire();
+                let v = m.mul(8, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(30);
+                m.retire();
+                let v = m.add(m.load(224), 6)?;
+                m.store(224, v);
+                m.set_ip(34);
+                m.retire();
+                let v = m.add(m.load(224), m.load(223))?;
+                m.store(223, v);
+                m.set_ip(38);
+                m.retire();
+                let v = m.add(67, 29)?;
+                m.store(225, v);
+                m.set_ip(42);
+                m.retire();
+                let v = m.add(m.load(14), m.load(65))?;
+                m.store(224, v);
+                m.set_ip(46);
+                m.retire();
+                let v = m.add(-124, m.load(224))?;
+                m.store(224, v);
+                m.set_ip(50);
+                m.retire();
+                m.output(m.load(224));
+                m.set_ip(52);
+                m.retire();
+                let v = m.mul(m.load(223), 8)?;
+                m.store(223, v);
+                m.set_ip(56);
+                m.retire();
+                let v = m.add(5, m.load(224))?;
+                m.store(224, v);
+                m.set_ip(60);
+                m.retire();
+                let v = m.add(m.load(224), m.load(223))?;
+                m.store(223, v);
+                m.set_ip(64);
+                m.retire();
+                let v = m.mul(63, 20)?;
+                m.store(225, v);
+                m.set_ip(68);
+                m.retire();
+                let v = m.mul(27, 15)?;
+                m.store(225, v);
+                m.set_ip(72);
+                m.retire();
+                let v = m.mul(18, 79)?;
+                m.store(224, v);
+                m.set_ip(76);
+                m.retire();
+                let v = m.add(-1422, m.load(224))?;
+                m.store(224, v);
+                m.set_ip(80);
+                m.retire();
+                m.output(m.load(224));
+                m.set_ip(82);
+                m.retire();
+                let v = m.mul(8, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(86);
+                m.retire();
+                let v = m.add(m.load(224), 1)?;
+                m.store(224, v);
+                m.set_ip(90);
+                m.retire();
+                let v = m.add(m.load(223), m.load(224))?;
+                m.store(223, v);
+                m.set_ip(94);
+                m.retire();
+                let v = m.mul(20, 44)?;
+                m.store(225, v);
+                m.set_ip(98);
+                m.retire();
+                let v = m.add(m.load(69), 5)?;
+                m.store(224, v);
+                m.set_ip(102);
+                m.retire();
+                let v = m.add(-32, m.load(224))?;
+                m.store(224, v);
+                m.set_ip(106);
+                m.retire();
+                m.output(m.load(224));
+                m.set_ip(108);
+                m.retire();
+                let v = m.mul(m.load(223), 8)?;
+                m.store(223, v);
+                m.set_ip(112);
+                m.retire();
+                let v = m.add(1, m.load(224))?;
+                m.store(224, v);
+                m.set_ip(116);
+                m.retire();
+                let v = m.add(m.load(223), m.load(224))?;
+                m.store(223, v);
+                m.set_ip(120);
+                m.retire();
+                let v = m.mul(15, 10)?;
+                m.store(225, v);
+                m.set_ip(124);
+                m.retire();
+                let v = m.add(6, 70)?;
+                m.store(225, v);
+                m.set_ip(128);
+                m.retire();
+                let v = m.mul(86, m.load(40))?;
+                m.store(224, v);
+                m.set_ip(132);
+                m.retire();
+                let v = m.add(-2494, m.load(224))?;
+                m.store(224, v);
+                m.set_ip(136);
+                m.retire();
+                m.output(m.load(224));
+                m.set_ip(138);
+                m.retire();
+                let v = m.mul(m.load(223), 8)?;
+                m.store(223, v);
+                m.set_ip(142);
+                m.retire();
+                let v = m.add(6, m.load(224))?;
+                m.store(224, v);
+                m.set_ip(146);
+                m.retire();
+                let v = m.add(m.load(223), m.load(224))?;
+                m.store(223, v);
+                m.set_ip(150);
+                m.retire();
+                let v = m.mul(25, 15)?;
+                m.store(225, v);
+                m.set_ip(154);
+                m.retire();
+                let v = m.add(40, 67)?;
+                m.store(224, v);
+                m.set_ip(158);
+                m.retire();
+                let v = m.add(m.load(224), -107)?;
+                m.store(224, v);
+                m.set_ip(162);
+                m.retire();
+                m.output(m.load(224));
+                m.set_ip(164);
+                m.retire();
+                let v = m.mul(8, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(168);
+                m.retire();
+                let v = m.add(1, m.load(224))?;
+                m.store(224, v);
+                m.set_ip(172);
+                m.retire();
+                let v = m.add(m.load(223), m.load(224))?;
+                m.store(223, v);
+                m.set_ip(176);
+                m.retire();
+                let v = m.mul(m.load(126), m.load(95))?;
+                m.store(224, v);
+                m.set_ip(180);
+                m.retire();
+                let v = m.add(-1400, m.load(224))?;
+                m.store(224, v);
+                m.set_ip(184);
+                m.retire();
+                m.output(m.load(224));
+                m.set_ip(186);
+                m.retire();
+                let v = m.mul(m.load(223), 8)?;
+                m.store(223, v);
+                m.set_ip(190);
+                m.retire();
+                let v = m.add(m.load(224), 3)?;
+                m.store(224, v);
+                m.set_ip(194);
+                m.retire();
+                let v = m.add(m.load(223), m.load(224))?;
+                m.store(223, v);
+                m.set_ip(198);
+                m.retire();
+                let v = m.mul(m.load(151), 84)?;
+                m.store(224, v);
+                m.set_ip(202);
+                m.retire();
+                let v = m.add(-2100, m.load(224))?;
+                m.store(224, v);
+                m.set_ip(206);
+                m.retire();
+                m.output(m.load(224));
+                m.set_ip(208);
+                m.retire();
+                let v = m.mul(8, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(212);
+                m.retire();
+                let v = m.add(6, m.load(224))?;
+                m.store(224, v);
+                m.set_ip(216);
+                m.retire();
+                let v = m.add(m.load(224), m.load(223))?;
+                m.store(223, v);
+                m.set_ip(220);
+                m.retire();
+                m.output(m.load(223));
+                m.set_ip(222);
+                m.retire();
+                return Ok(m.halt());
+            }
+            Some(238) => {
+                m.set_ip(238);
+                m.retire();
+                m.set_ip(241);
+            }
+            Some(241) => {
+                m.set_ip(241);
+                m.retire();
+                m.set_ip(247);
+            }
+            Some(244) => {
+                m.set_ip(244);
+                m.retire();
+                m.set_ip(99999);
+            }
+            Some(247) => {
+                m.set_ip(247);
+                m.retire();
+                if m.load(227) != 0 {
+                    m.set_ip(99999);
+                    continue;
+                }
+                m.set_ip(250);
+            }
+            Some(250) => {
+                m.set_ip(250);
+                m.retire();
+                if m.load(0) != 0 {
+                    m.set_ip(256);
+                    continue;
+                }
+                m.set_ip(253);
+            }
+            Some(253) => {
+                m.set_ip(253);
+                m.retire();
+                m.set_ip(99999);
+            }
+            Some(256) => {
+                m.set_ip(256);
+                m.retire();
+                m.set_ip(259);
+            }
+            Some(259) => {
+                m.set_ip(259);
+                m.retire();
+                m.set_ip(265);
+            }
+            Some(262) => {
+                m.set_ip(262);
+                m.retire();
+                m.set_ip(99999);
+            }
+            Some(265) => {
+                m.set_ip(265);
+                m.retire();
+                if m.load(0) == 0 {
+                    m.set_ip(99999);
+                    continue;
+                }
+                m.set_ip(268);
+            }
+            Some(268) => {
+                m.set_ip(268);
+                m.retire();
+                if m.load(227) == 0 {
+                    m.set_ip(274);
+                    continue;
+                }
+                m.set_ip(271);
+            }
+            Some(271) => {
+                m.set_ip(271);
+                m.retire();
+                m.set_ip(99999);
+            }
+            Some(274) => {
+                m.set_ip(274);
+                m.retire();
+                m.set_ip(280);
+            }
+            Some(277) => {
+                m.set_ip(277);
+                m.retire();
+                m.set_ip(99999);
+            }
+            Some(280) => {
+                m.set_ip(280);
+                m.retire();
+                let v = m.add(m.load(225), m.load(225))?;
+                m.store(225, v);
+                m.set_ip(284);
+                m.retire();
+                let v = m.add(294, 0)?;
+                m.patch(0, v);
+                m.set_ip(288);
+                continue;
+            }
+            Some(291) => {
+                m.set_ip(291);
+                m.retire();
+                m.set_ip(99999);
+            }
+            Some(294) => {
+                m.set_ip(294);
+                m.retire();
+                m.set_ip(300);
+            }
+            Some(297) => {
+                m.set_ip(297);
+                m.retire();
+                m.set_ip(99999);
+            }
+            Some(300) => {
+                m.set_ip(300);
+                m.retire();
+                let v = m.add(m.load(225), m.load(225))?;
+                m.store(225, v);
+                m.set_ip(304);
+                m.retire();
+                let v = m.add(314, 0)?;
+                m.patch(0, v);
+                m.set_ip(308);
+                continue;
+            }
+            Some(311) => {
+                m.set_ip(311);
+                m.retire();
+                m.set_ip(99999);
+            }
+            Some(314) => {
+                m.set_ip(314);
+                m.retire();
+                let v = (677 == m.load(677)) as i64;
+                m.store(224, v);
+                m.set_ip(318);
+                m.retire();
+                let v = m.mul(m.load(223), 2)?;
+                m.store(223, v);
+                m.set_ip(322);
+                m.retire();
+                if m.load(224) == 0 {
+                    m.set_ip(329);
+                    continue;
+                }
+                m.set_ip(325);
+            }
+            Some(325) => {
+                m.set_ip(325);
+                m.retire();
+                let v = m.add(1, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(329);
+            }
+            Some(329) => {
+                m.set_ip(329);
+                m.retire();
+                let v = (677 < 226) as i64;
+                m.store(224, v);
+                m.set_ip(333);
+                m.retire();
+                let v = m.mul(2, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(337);
+                m.retire();
+                if m.load(224) == 0 {
+                    m.set_ip(344);
+                    continue;
+                }
+                m.set_ip(340);
+            }
+            Some(340) => {
+                m.set_ip(340);
+                m.retire();
+                let v = m.add(1, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(344);
+            }
+            Some(344) => {
+                m.set_ip(344);
+                m.retire();
+                let v = (m.load(677) == m.load(677)) as i64;
+                m.store(224, v);
+                m.set_ip(348);
+                m.retire();
+                let v = m.mul(m.load(223), 2)?;
+                m.store(223, v);
+                m.set_ip(352);
+                m.retire();
+                if m.load(224) == 0 {
+                    m.set_ip(359);
+                    continue;
+                }
+                m.set_ip(355);
+            }
+            Some(355) => {
+                m.set_ip(355);
+                m.retire();
+                let v = m.add(1, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(359);
+            }
+            Some(359) => {
+                m.set_ip(359);
+                m.retire();
+                let v = (m.load(677) == 677) as i64;
+                m.store(224, v);
+                m.set_ip(363);
+                m.retire();
+                let v = m.mul(m.load(223), 2)?;
+                m.store(223, v);
+                m.set_ip(367);
+                m.retire();
+                if m.load(224) == 0 {
+                    m.set_ip(374);
+                    continue;
+                }
+                m.set_ip(370);
+            }
+            Some(370) => {
+                m.set_ip(370);
+                m.retire();
+                let v = m.add(1, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(374);
+            }
+            Some(374) => {
+                m.set_ip(374);
+                m.retire();
+                let v = (m.load(226) < m.load(677)) as i64;
+                m.store(224, v);
+                m.set_ip(378);
+                m.retire();
+                let v = m.mul(m.load(223), 2)?;
+                m.store(223, v);
+                m.set_ip(382);
+                m.retire();
+                if m.load(224) == 0 {
+                    m.set_ip(389);
+                    continue;
+                }
+                m.set_ip(385);
+            }
+            Some(385) => {
+                m.set_ip(385);
+                m.retire();
+                let v = m.add(m.load(223), 1)?;
+                m.store(223, v);
+                m.set_ip(389);
+            }
+            Some(389) => {
+                m.set_ip(389);
+                m.retire();
+                let v = (m.load(677) < 677) as i64;
+                m.store(224, v);
+                m.set_ip(393);
+                m.retire();
+                let v = m.mul(m.load(223), 2)?;
+                m.store(223, v);
+                m.set_ip(397);
+                m.retire();
+                if m.load(224) == 0 {
+                    m.set_ip(404);
+                    continue;
+                }
+                m.set_ip(400);
+            }
+            Some(400) => {
+                m.set_ip(400);
+                m.retire();
+                let v = m.add(m.load(223), 1)?;
+                m.store(223, v);
+                m.set_ip(404);
+            }
+            Some(404) => {
+                m.set_ip(404);
+                m.retire();
+                let v = (m.load(677) < m.load(677)) as i64;
+                m.store(224, v);
+                m.set_ip(408);
+                m.retire();
+                let v = m.mul(m.load(223), 2)?;
+                m.store(223, v);
+                m.set_ip(412);
+                m.retire();
+                if m.load(224) == 0 {
+                    m.set_ip(419);
+                    continue;
+                }
+                m.set_ip(415);
+            }
+            Some(415) => {
+                m.set_ip(415);
+                m.retire();
+                let v = m.add(m.load(223), 1)?;
+                m.store(223, v);
+                m.set_ip(419);
+            }
+            Some(419) => {
+                m.set_ip(419);
+                m.retire();
+                let v = (m.load(677) == 226) as i64;
+                m.store(224, v);
+                m.set_ip(423);
+                m.retire();
+                let v = m.mul(m.load(223), 2)?;
+                m.store(223, v);
+                m.set_ip(427);
+                m.retire();
+                if m.load(224) != 0 {
+                    m.set_ip(434);
+                    continue;
+                }
+                m.set_ip(430);
+            }
+            Some(430) => {
+                m.set_ip(430);
+                m.retire();
+                let v = m.add(m.load(223), 1)?;
+                m.store(223, v);
+                m.set_ip(434);
+            }
+            Some(434) => {
+                m.set_ip(434);
+                m.retire();
+                let v = (226 < 677) as i64;
+                m.store(224, v);
+                m.set_ip(438);
+                m.retire();
+                let v = m.mul(2, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(442);
+                m.retire();
+                if m.load(224) != 0 {
+                    m.set_ip(449);
+                    continue;
+                }
+                m.set_ip(445);
+            }
+            Some(445) => {
+                m.set_ip(445);
+                m.retire();
+                let v = m.add(m.load(223), 1)?;
+                m.store(223, v);
+                m.set_ip(449);
+            }
+            Some(449) => {
+                m.set_ip(449);
+                m.retire();
+                let v = (m.load(226) == 226) as i64;
+                m.store(224, v);
+                m.set_ip(453);
+                m.retire();
+                let v = m.mul(m.load(223), 2)?;
+                m.store(223, v);
+                m.set_ip(457);
+                m.retire();
+                if m.load(224) == 0 {
+                    m.set_ip(464);
+                    continue;
+                }
+                m.set_ip(460);
+            }
+            Some(460) => {
+                m.set_ip(460);
+                m.retire();
+                let v = m.add(m.load(223), 1)?;
+                m.store(223, v);
+                m.set_ip(464);
+            }
+            Some(464) => {
+                m.set_ip(464);
+                m.retire();
+                let v = (677 == 677) as i64;
+                m.store(224, v);
+                m.set_ip(468);
+                m.retire();
+                let v = m.mul(2, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(472);
+                m.retire();
+                if m.load(224) == 0 {
+                    m.set_ip(479);
+                    continue;
+                }
+                m.set_ip(475);
+            }
+            Some(475) => {
+                m.set_ip(475);
+                m.retire();
+                let v = m.add(1, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(479);
+            }
+            Some(479) => {
+                m.set_ip(479);
+                m.retire();
+                let v = (226 == 677) as i64;
+                m.store(224, v);
+                m.set_ip(483);
+                m.retire();
+                let v = m.mul(m.load(223), 2)?;
+                m.store(223, v);
+                m.set_ip(487);
+                m.retire();
+                if m.load(224) == 0 {
+                    m.set_ip(494);
+                    continue;
+                }
+                m.set_ip(490);
+            }
+            Some(490) => {
+                m.set_ip(490);
+                m.retire();
+                let v = m.add(m.load(223), 1)?;
+                m.store(223, v);
+                m.set_ip(494);
+            }
+            Some(494) => {
+                m.set_ip(494);
+                m.retire();
+                let v = (226 < m.load(226)) as i64;
+                m.store(224, v);
+                m.set_ip(498);
+                m.retire();
+                let v = m.mul(m.load(223), 2)?;
+                m.store(223, v);
+                m.set_ip(502);
+                m.retire();
+                if m.load(224) == 0 {
+                    m.set_ip(509);
+                    continue;
+                }
+                m.set_ip(505);
+            }
+            Some(505) => {
+                m.set_ip(505);
+                m.retire();
+                let v = m.add(m.load(223), 1)?;
+                m.store(223, v);
+                m.set_ip(509);
+            }
+            Some(509) => {
+                m.set_ip(509);
+                m.retire();
+                let v = (m.load(226) == m.load(677)) as i64;
+                m.store(224, v);
+                m.set_ip(513);
+                m.retire();
+                let v = m.mul(2, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(517);
+                m.retire();
+                if m.load(224) == 0 {
+                    m.set_ip(524);
+                    continue;
+                }
+                m.set_ip(520);
+            }
+            Some(520) => {
+                m.set_ip(520);
+                m.retire();
+                let v = m.add(m.load(223), 1)?;
+                m.store(223, v);
+                m.set_ip(524);
+            }
+            Some(524) => {
+                m.set_ip(524);
+                m.retire();
+                let v = (m.load(226) < 226) as i64;
+                m.store(224, v);
+                m.set_ip(528);
+                m.retire();
+                let v = m.mul(m.load(223), 2)?;
+                m.store(223, v);
+                m.set_ip(532);
+                m.retire();
+                if m.load(224) == 0 {
+                    m.set_ip(539);
+                    continue;
+                }
+                m.set_ip(535);
+            }
+            Some(535) => {
+                m.set_ip(535);
+                m.retire();
+                let v = m.add(m.load(223), 1)?;
+                m.store(223, v);
+                m.set_ip(539);
+            }
+            Some(539) => {
+                m.set_ip(539);
+                m.retire();
+                let v = (677 < m.load(677)) as i64;
+                m.store(224, v);
+                m.set_ip(543);
+                m.retire();
+                let v = m.mul(m.load(223), 2)?;
+                m.store(223, v);
+                m.set_ip(547);
+                m.retire();
+                if m.load(224) == 0 {
+                    m.set_ip(554);
+                    continue;
+                }
+                m.set_ip(550);
+            }
+            Some(550) => {
+                m.set_ip(550);
+                m.retire();
+                let v = m.add(m.load(223), 1)?;
+                m.store(223, v);
+                m.set_ip(554);
+            }
+            Some(554) => {
+                m.set_ip(554);
+                m.retire();
+                let v = (226 < 226) as i64;
+                m.store(224, v);
+                m.set_ip(558);
+                m.retire();
+                let v = m.mul(2, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(562);
+                m.retire();
+                if m.load(224) != 0 {
+                    m.set_ip(569);
+                    continue;
+                }
+                m.set_ip(565);
+            }
+            Some(565) => {
+                m.set_ip(565);
+                m.retire();
+                let v = m.add(1, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(569);
+            }
+            Some(569) => {
+                m.set_ip(569);
+                m.retire();
+                let v = (677 == 226) as i64;
+                m.store(224, v);
+                m.set_ip(573);
+                m.retire();
+                let v = m.mul(m.load(223), 2)?;
+                m.store(223, v);
+                m.set_ip(577);
+                m.retire();
+                if m.load(224) == 0 {
+                    m.set_ip(584);
+                    continue;
+                }
+                m.set_ip(580);
+            }
+            Some(580) => {
+                m.set_ip(580);
+                m.retire();
+                let v = m.add(m.load(223), 1)?;
+                m.store(223, v);
+                m.set_ip(584);
+            }
+            Some(584) => {
+                m.set_ip(584);
+                m.retire();
+                let v = (m.load(677) < 226) as i64;
+                m.store(224, v);
+                m.set_ip(588);
+                m.retire();
+                let v = m.mul(m.load(223), 2)?;
+                m.store(223, v);
+                m.set_ip(592);
+                m.retire();
+                if m.load(224) != 0 {
+                    m.set_ip(599);
+                    continue;
+                }
+                m.set_ip(595);
+            }
+            Some(595) => {
+                m.set_ip(595);
+                m.retire();
+                let v = m.add(1, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(599);
+            }
+            Some(599) => {
+                m.set_ip(599);
+                m.retire();
+                let v = (226 < m.load(677)) as i64;
+                m.store(224, v);
+                m.set_ip(603);
+                m.retire();
+                let v = m.mul(2, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(607);
+                m.retire();
+                if m.load(224) != 0 {
+                    m.set_ip(614);
+                    continue;
+                }
+                m.set_ip(610);
+            }
+            Some(610) => {
+                m.set_ip(610);
+                m.retire();
+                let v = m.add(m.load(223), 1)?;
+                m.store(223, v);
+                m.set_ip(614);
+            }
+            Some(614) => {
+                m.set_ip(614);
+                m.retire();
+                let v = (226 == m.load(226)) as i64;
+                m.store(224, v);
+                m.set_ip(618);
+                m.retire();
+                let v = m.mul(m.load(223), 2)?;
+                m.store(223, v);
+                m.set_ip(622);
+                m.retire();
+                if m.load(224) != 0 {
+                    m.set_ip(629);
+                    continue;
+                }
+                m.set_ip(625);
+            }
+            Some(625) => {
+                m.set_ip(625);
+                m.retire();
+                let v = m.add(1, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(629);
+            }
+            Some(629) => {
+                m.set_ip(629);
+                m.retire();
+                let v = (m.load(677) < m.load(226)) as i64;
+                m.store(224, v);
+                m.set_ip(633);
+                m.retire();
+                let v = m.mul(2, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(637);
+                m.retire();
+                if m.load(224) != 0 {
+                    m.set_ip(644);
+                    continue;
+                }
+                m.set_ip(640);
+            }
+            Some(640) => {
+                m.set_ip(640);
+                m.retire();
+                let v = m.add(1, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(644);
+            }
+            Some(644) => {
+                m.set_ip(644);
+                m.retire();
+                let v = (m.load(677) == m.load(226)) as i64;
+                m.store(224, v);
+                m.set_ip(648);
+                m.retire();
+                let v = m.mul(2, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(652);
+                m.retire();
+                if m.load(224) == 0 {
+                    m.set_ip(659);
+                    continue;
+                }
+                m.set_ip(655);
+            }
+            Some(655) => {
+                m.set_ip(655);
+                m.retire();
+                let v = m.add(m.load(223), 1)?;
+                m.store(223, v);
+                m.set_ip(659);
+            }
+            Some(659) => {
+                m.set_ip(659);
+                m.retire();
+                let v = (677 == m.load(226)) as i64;
+                m.store(224, v);
+                m.set_ip(663);
+                m.retire();
+                let v = m.mul(2, m.load(223))?;
+                m.store(223, v);
+                m.set_ip(667);
+                m.retire();
+                if m.load(224) != 0 {
+                    m.set_ip(674);
+                    continue;
+                }
+                m.set_ip(670);
+            }
+            Some(670) => {
+                m.set_ip(670);
+                m.retire();
+                let v = m.add(m.load(223), 1)?;
+                m.store(223, v);
+                m.set_ip(674);
+            }
+            Some(674) => {
+                m.set_ip(674);
+                m.retire();
+                m.output(m.load(223));
+                m.set_ip(676);
+                m.retire();
+                return Ok(m.halt());
+            }
+            _ => {
+                if let Some(state) = m.interpret()? {
+                    return Ok(state);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,918 @@
+// Generated by intcode-aot, do not edit.
+#![allow(clippy::all, unused_parens)]
+
+use advent_of_code_2019::{AotMachine, IntcodeComputer, IntcodeError, RunState};
+
+static CODE: &[(usize, &[i64])] = &[
+    (0, &[
+        3, 8, 1001, 8, 10, 8, 105, 1, 0,
+    ]),
+    (15, &[
+        106, 187, 268,
+    ]),
+    (21, &[
+        3, 9, 102, 5, 9, 9, 1001, 9, 2, 9, 4, 9, 99, 3, 9, 1001,
+        9, 5, 9, 102, 2, 9, 9, 101, 2, 9, 9, 102, 2, 9, 9, 4,
+        9, 99, 3, 9, 101, 2, 9, 9, 102, 4, 9, 9, 4, 9, 99, 3,
+        9, 101, 4, 9, 9, 102, 3, 9, 9, 1001, 9, 2, 9, 102, 4, 9,
+        9, 1001, 9, 2, 9, 4, 9, 99, 3, 9, 101, 2, 9, 9, 1002, 9,
+        5, 9, 4, 9, 99, 3, 9, 101, 1, 9, 9, 4, 9, 3, 9, 1002,
+        9, 2, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 1002,
+        9, 2, 9, 4, 9, 3, 9, 101, 2, 9, 9, 4, 9, 3, 9, 1001,
+        9, 2, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 102,
+        2, 9, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 101,
+        1, 9, 9, 4, 9, 99, 3, 9, 101, 2, 9, 9, 4, 9, 3, 9,
+        1001, 9, 1, 9, 4, 9, 3, 9, 101, 1, 9, 9, 4, 9, 3, 9,
+        1001, 9, 1, 9, 4, 9, 3, 9, 1001, 9, 2, 9, 4, 9, 3, 9,
+        102, 2, 9, 9, 4, 9, 3, 9, 1001, 9, 1, 9, 4, 9, 3, 9,
+        102, 2, 9, 9, 4, 9, 3, 9, 1001, 9, 1, 9, 4, 9, 3, 9,
+        101, 2, 9, 9, 4, 9, 99, 3, 9, 102, 2, 9, 9, 4, 9, 3,
+        9, 102, 2, 9, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3,
+        9, 1002, 9, 2, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3,
+        9, 101, 1, 9, 9, 4, 9, 3, 9, 101, 1, 9, 9, 4, 9, 3,
+        9, 101, 1, 9, 9, 4, 9, 3, 9, 101, 2, 9, 9, 4, 9, 3,
+        9, 1001, 9, 2, 9, 4, 9, 99, 3, 9, 1002, 9, 2, 9, 4, 9,
+        3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 1001, 9, 2, 9, 4, 9,
+        3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9,
+        3, 9, 101, 2, 9, 9, 4, 9, 3, 9, 1001, 9, 2, 9, 4, 9,
+        3, 9, 101, 1, 9, 9, 4, 9, 3, 9, 101, 1, 9, 9, 4, 9,
+        3, 9, 101, 1, 9, 9, 4, 9, 99, 3, 9, 101, 2, 9, 9, 4,
+        9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4,
+        9, 3, 9, 101, 1, 9, 9, 4, 9, 3, 9, 1001, 9, 1, 9, 4,
+        9, 3, 9, 101, 2, 9, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4,
+        9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4,
+        9, 3, 9, 1002, 9, 2, 9, 4, 9, 99,
+    ]),
+];
+
+static BLOCKS: &[(usize, usize, usize)] = &[
+    (0, 2, 3), (15, 18, 1), (21, 34, 5), (34, 55, 7), (55, 68, 5), (68, 93, 8), (93, 106, 5), (106, 114, 3),
+    (114, 122, 3), (122, 130, 3), (130, 138, 3), (138, 146, 3), (146, 154, 3), (154, 162, 3), (162, 170, 3), (170, 178, 3),
+    (178, 187, 4), (187, 195, 3), (195, 203, 3), (203, 211, 3), (211, 219, 3), (219, 227, 3), (227, 235, 3), (235, 243, 3),
+    (243, 251, 3), (251, 259, 3), (259, 268, 4), (268, 276, 3), (276, 284, 3), (284, 292, 3), (292, 300, 3), (300, 308, 3),
+    (308, 316, 3), (316, 324, 3), (324, 332, 3), (332, 340, 3), (340, 349, 4), (349, 357, 3), (357, 365, 3), (365, 373, 3),
+    (373, 381, 3), (381, 389, 3), (389, 397, 3), (397, 405, 3), (405, 413, 3), (413, 421, 3), (421, 430, 4), (430, 438, 3),
+    (438, 446, 3), (446, 454, 3), (454, 462, 3), (462, 470, 3), (470, 478, 3), (478, 486, 3), (486, 494, 3), (494, 502, 3),
+    (502, 511, 4),
+];
+
+pub fn run(computer: &mut IntcodeComputer) -> Result<RunState, IntcodeError> {
+    let mut m = AotMachine::new(computer, CODE, BLOCKS);
+    let result = execute(&mut m);
+    m.finish(result)
+}
+
+fn execute(m: &mut AotMachine) -> Result<RunState, IntcodeError> {
+    loop {
+        match m.block() {
+            Some(0) => {
+                m.set_ip(0);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.patch(8, v);
+                m.set_ip(2);
+                continue;
+            }
+            Some(15) => {
+                m.set_ip(15);
+                m.retire();
+                m.set_ip(18);
+            }
+            Some(21) => {
+                m.set_ip(21);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(23);
+                m.retire();
+                let v = m.mul(5, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(27);
+                m.retire();
+                let v = m.add(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(31);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(33);
+                m.retire();
+                return Ok(m.halt());
+            }
+            Some(34) => {
+                m.set_ip(34);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(36);
+                m.retire();
+                let v = m.add(m.load(9), 5)?;
+                m.store(9, v);
+                m.set_ip(40);
+                m.retire();
+                let v = m.mul(2, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(44);
+                m.retire();
+                let v = m.add(2, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(48);
+                m.retire();
+                let v = m.mul(2, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(52);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(54);
+                m.retire();
+                return Ok(m.halt());
+            }
+            Some(55) => {
+                m.set_ip(55);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(57);
+                m.retire();
+                let v = m.add(2, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(61);
+                m.retire();
+                let v = m.mul(4, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(65);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(67);
+                m.retire();
+                return Ok(m.halt());
+            }
+            Some(68) => {
+                m.set_ip(68);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(70);
+                m.retire();
+                let v = m.add(4, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(74);
+                m.retire();
+                let v = m.mul(3, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(78);
+                m.retire();
+                let v = m.add(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(82);
+                m.retire();
+                let v = m.mul(4, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(86);
+                m.retire();
+                let v = m.add(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(90);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(92);
+                m.retire();
+                return Ok(m.halt());
+            }
+            Some(93) => {
+                m.set_ip(93);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(95);
+                m.retire();
+                let v = m.add(2, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(99);
+                m.retire();
+                let v = m.mul(m.load(9), 5)?;
+                m.store(9, v);
+                m.set_ip(103);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(105);
+                m.retire();
+                return Ok(m.halt());
+            }
+            Some(106) => {
+                m.set_ip(106);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(108);
+                m.retire();
+                let v = m.add(1, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(112);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(114);
+            }
+            Some(114) => {
+                m.set_ip(114);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(116);
+                m.retire();
+                let v = m.mul(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(120);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(122);
+            }
+            Some(122) => {
+                m.set_ip(122);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(124);
+                m.retire();
+                let v = m.mul(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(128);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(130);
+            }
+            Some(130) => {
+                m.set_ip(130);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(132);
+                m.retire();
+                let v = m.mul(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(136);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(138);
+            }
+            Some(138) => {
+                m.set_ip(138);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(140);
+                m.retire();
+                let v = m.add(2, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(144);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(146);
+            }
+            Some(146) => {
+                m.set_ip(146);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(148);
+                m.retire();
+                let v = m.add(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(152);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(154);
+            }
+            Some(154) => {
+                m.set_ip(154);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(156);
+                m.retire();
+                let v = m.mul(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(160);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(162);
+            }
+            Some(162) => {
+                m.set_ip(162);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(164);
+                m.retire();
+                let v = m.mul(2, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(168);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(170);
+            }
+            Some(170) => {
+                m.set_ip(170);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(172);
+                m.retire();
+                let v = m.mul(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(176);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(178);
+            }
+            Some(178) => {
+                m.set_ip(178);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(180);
+                m.retire();
+                let v = m.add(1, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(184);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(186);
+                m.retire();
+                return Ok(m.halt());
+            }
+            Some(187) => {
+                m.set_ip(187);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(189);
+                m.retire();
+                let v = m.add(2, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(193);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(195);
+            }
+            Some(195) => {
+                m.set_ip(195);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(197);
+                m.retire();
+                let v = m.add(m.load(9), 1)?;
+                m.store(9, v);
+                m.set_ip(201);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(203);
+            }
+            Some(203) => {
+                m.set_ip(203);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(205);
+                m.retire();
+                let v = m.add(1, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(209);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(211);
+            }
+            Some(211) => {
+                m.set_ip(211);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(213);
+                m.retire();
+                let v = m.add(m.load(9), 1)?;
+                m.store(9, v);
+                m.set_ip(217);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(219);
+            }
+            Some(219) => {
+                m.set_ip(219);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(221);
+                m.retire();
+                let v = m.add(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(225);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(227);
+            }
+            Some(227) => {
+                m.set_ip(227);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(229);
+                m.retire();
+                let v = m.mul(2, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(233);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(235);
+            }
+            Some(235) => {
+                m.set_ip(235);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(237);
+                m.retire();
+                let v = m.add(m.load(9), 1)?;
+                m.store(9, v);
+                m.set_ip(241);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(243);
+            }
+            Some(243) => {
+                m.set_ip(243);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(245);
+                m.retire();
+                let v = m.mul(2, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(249);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(251);
+            }
+            Some(251) => {
+                m.set_ip(251);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(253);
+                m.retire();
+                let v = m.add(m.load(9), 1)?;
+                m.store(9, v);
+                m.set_ip(257);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(259);
+            }
+            Some(259) => {
+                m.set_ip(259);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(261);
+                m.retire();
+                let v = m.add(2, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(265);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(267);
+                m.retire();
+                return Ok(m.halt());
+            }
+            Some(268) => {
+                m.set_ip(268);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(270);
+                m.retire();
+                let v = m.mul(2, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(274);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(276);
+            }
+            Some(276) => {
+                m.set_ip(276);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(278);
+                m.retire();
+                let v = m.mul(2, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(282);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(284);
+            }
+            Some(284) => {
+                m.set_ip(284);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(286);
+                m.retire();
+                let v = m.mul(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(290);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(292);
+            }
+            Some(292) => {
+                m.set_ip(292);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(294);
+                m.retire();
+                let v = m.mul(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(298);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(300);
+            }
+            Some(300) => {
+                m.set_ip(300);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(302);
+                m.retire();
+                let v = m.mul(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(306);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(308);
+            }
+            Some(308) => {
+                m.set_ip(308);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(310);
+                m.retire();
+                let v = m.add(1, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(314);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(316);
+            }
+            Some(316) => {
+                m.set_ip(316);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(318);
+                m.retire();
+                let v = m.add(1, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(322);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(324);
+            }
+            Some(324) => {
+                m.set_ip(324);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(326);
+                m.retire();
+                let v = m.add(1, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(330);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(332);
+            }
+            Some(332) => {
+                m.set_ip(332);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(334);
+                m.retire();
+                let v = m.add(2, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(338);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(340);
+            }
+            Some(340) => {
+                m.set_ip(340);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(342);
+                m.retire();
+                let v = m.add(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(346);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(348);
+                m.retire();
+                return Ok(m.halt());
+            }
+            Some(349) => {
+                m.set_ip(349);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(351);
+                m.retire();
+                let v = m.mul(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(355);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(357);
+            }
+            Some(357) => {
+                m.set_ip(357);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(359);
+                m.retire();
+                let v = m.mul(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(363);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(365);
+            }
+            Some(365) => {
+                m.set_ip(365);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(367);
+                m.retire();
+                let v = m.add(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(371);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(373);
+            }
+            Some(373) => {
+                m.set_ip(373);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(375);
+                m.retire();
+                let v = m.mul(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(379);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(381);
+            }
+            Some(381) => {
+                m.set_ip(381);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(383);
+                m.retire();
+                let v = m.mul(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(387);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(389);
+            }
+            Some(389) => {
+                m.set_ip(389);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(391);
+                m.retire();
+                let v = m.add(2, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(395);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(397);
+            }
+            Some(397) => {
+                m.set_ip(397);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(399);
+                m.retire();
+                let v = m.add(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(403);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(405);
+            }
+            Some(405) => {
+                m.set_ip(405);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(407);
+                m.retire();
+                let v = m.add(1, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(411);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(413);
+            }
+            Some(413) => {
+                m.set_ip(413);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(415);
+                m.retire();
+                let v = m.add(1, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(419);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(421);
+            }
+            Some(421) => {
+                m.set_ip(421);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(423);
+                m.retire();
+                let v = m.add(1, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(427);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(429);
+                m.retire();
+                return Ok(m.halt());
+            }
+            Some(430) => {
+                m.set_ip(430);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(432);
+                m.retire();
+                let v = m.add(2, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(436);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(438);
+            }
+            Some(438) => {
+                m.set_ip(438);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(440);
+                m.retire();
+                let v = m.mul(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(444);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(446);
+            }
+            Some(446) => {
+                m.set_ip(446);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(448);
+                m.retire();
+                let v = m.mul(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(452);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(454);
+            }
+            Some(454) => {
+                m.set_ip(454);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(456);
+                m.retire();
+                let v = m.add(1, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(460);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(462);
+            }
+            Some(462) => {
+                m.set_ip(462);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(464);
+                m.retire();
+                let v = m.add(m.load(9), 1)?;
+                m.store(9, v);
+                m.set_ip(468);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(470);
+            }
+            Some(470) => {
+                m.set_ip(470);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(472);
+                m.retire();
+                let v = m.add(2, m.load(9))?;
+                m.store(9, v);
+                m.set_ip(476);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(478);
+            }
+            Some(478) => {
+                m.set_ip(478);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(480);
+                m.retire();
+                let v = m.mul(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(484);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(486);
+            }
+            Some(486) => {
+                m.set_ip(486);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(488);
+                m.retire();
+                let v = m.mul(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(492);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(494);
+            }
+            Some(494) => {
+                m.set_ip(494);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(496);
+                m.retire();
+                let v = m.mul(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(500);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(502);
+            }
+            Some(502) => {
+                m.set_ip(502);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                m.store(9, v);
+                m.set_ip(504);
+                m.retire();
+                let v = m.mul(m.load(9), 2)?;
+                m.store(9, v);
+                m.set_ip(508);
+                m.retire();
+                m.output(m.load(9));
+                m.set_ip(510);
+                m.retire();
+                return Ok(m.halt());
+            }
+            _ => {
+                if let Some(state) = m.interpret()? {
+                    return Ok(state);
+                }
+            }
+        }
+    }
+}
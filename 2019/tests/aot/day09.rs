@@ -0,0 +1,1600 @@
+// Generated by intcode-aot, do not edit.
+#![allow(clippy::all, unused_parens)]
+
+use advent_of_code_2019::{AotMachine, IntcodeComputer, IntcodeError, RunState};
+
+static CODE: &[(usize, &[i64])] = &[
+    (0, &[
+        1102, 34463338, 34463338, 63, 1007, 63, 34463338, 63, 1005, 63, 53, 1101, 3, 0, 1000, 109,
+        988, 209, 12, 9, 1000, 209, 6, 209, 3, 203, 0, 1008, 1000, 1, 63, 1005,
+        63, 65, 1008, 1000, 2, 63, 1005, 63, 902, 1008, 1000, 0, 63, 1005, 63, 58,
+        4, 25, 104, 0, 99, 4, 0, 104, 0, 99, 4, 17, 104, 0, 99,
+    ]),
+    (65, &[
+        1101, 26, 0, 1015, 1101, 29, 0, 1010, 1102, 1, 24, 1013, 1102, 1, 33, 1008,
+        1102, 36, 1, 1012, 1101, 0, 572, 1023, 1101, 35, 0, 1014, 1101, 0, 38, 1019,
+        1102, 1, 30, 1006, 1101, 0, 890, 1029, 1101, 34, 0, 1011, 1101, 28, 0, 1002,
+        1102, 1, 1, 1021, 1101, 0, 37, 1001, 1101, 0, 197, 1026, 1101, 22, 0, 1017,
+        1102, 1, 895, 1028, 1101, 0, 20, 1007, 1102, 21, 1, 1004, 1102, 1, 39, 1016,
+        1101, 0, 0, 1020, 1102, 1, 190, 1027, 1101, 0, 775, 1024, 1102, 31, 1, 1018,
+        1101, 0, 23, 1003, 1101, 0, 25, 1009, 1101, 770, 0, 1025, 1101, 0, 27, 1000,
+        1102, 1, 575, 1022, 1101, 0, 32, 1005, 109, 27, 2106, 0, 0, 1001, 64, 1,
+        64, 1106, 0, 199, 4, 187, 1002, 64, 2, 64, 109, -18, 21101, 40, 0, 5,
+        1008, 1014, 39, 63, 1005, 63, 219, 1106, 0, 225, 4, 205, 1001, 64, 1, 64,
+        1002, 64, 2, 64, 109, -6, 1201, -1, 0, 63, 1008, 63, 28, 63, 1005, 63,
+        251, 4, 231, 1001, 64, 1, 64, 1105, 1, 251, 1002, 64, 2, 64, 109, 5,
+        21102, 41, 1, 3, 1008, 1011, 38, 63, 1005, 63, 271, 1105, 1, 277, 4, 257,
+        1001, 64, 1, 64, 1002, 64, 2, 64, 109, -7, 2102, 1, 1, 63, 1008, 63,
+        28, 63, 1005, 63, 299, 4, 283, 1106, 0, 303, 1001, 64, 1, 64, 1002, 64,
+        2, 64, 109, -7, 1207, 10, 22, 63, 1005, 63, 321, 4, 309, 1106, 0, 325,
+        1001, 64, 1, 64, 1002, 64, 2, 64, 109, 16, 2107, 31, -4, 63, 1005, 63,
+        345, 1001, 64, 1, 64, 1105, 1, 347, 4, 331, 1002, 64, 2, 64, 109, -9,
+        1201, 3, 0, 63, 1008, 63, 18, 63, 1005, 63, 371, 1001, 64, 1, 64, 1106,
+        0, 373, 4, 353, 1002, 64, 2, 64, 109, 7, 1202, -7, 1, 63, 1008, 63,
+        40, 63, 1005, 63, 393, 1106, 0, 399, 4, 379, 1001, 64, 1, 64, 1002, 64,
+        2, 64, 109, -5, 1208, 5, 33, 63, 1005, 63, 417, 4, 405, 1106, 0, 421,
+        1001, 64, 1, 64, 1002, 64, 2, 64, 109, 1, 1202, 2, 1, 63, 1008, 63,
+        30, 63, 1005, 63, 443, 4, 427, 1105, 1, 447, 1001, 64, 1, 64, 1002, 64,
+        2, 64, 109, -7, 2102, 1, 10, 63, 1008, 63, 19, 63, 1005, 63, 471, 1001,
+        64, 1, 64, 1105, 1, 473, 4, 453, 1002, 64, 2, 64, 109, 6, 2108, 21,
+        0, 63, 1005, 63, 489, 1105, 1, 495, 4, 479, 1001, 64, 1, 64, 1002, 64,
+        2, 64, 109, 9, 21108, 42, 42, 0, 1005, 1012, 513, 4, 501, 1105, 1, 517,
+        1001, 64, 1, 64, 1002, 64, 2, 64, 109, 7, 21107, 43, 44, -1, 1005, 1018,
+        535, 4, 523, 1106, 0, 539, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -5,
+        21101, 44, 0, 2, 1008, 1016, 44, 63, 1005, 63, 561, 4, 545, 1105, 1, 565,
+        1001, 64, 1, 64, 1002, 64, 2, 64, 2105, 1, 9, 1106, 0, 581, 4, 569,
+        1001, 64, 1, 64, 1002, 64, 2, 64, 109, 13, 21107, 45, 44, -9, 1005, 1018,
+        597, 1105, 1, 603, 4, 587, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -25,
+        2101, 0, 3, 63, 1008, 63, 32, 63, 1005, 63, 625, 4, 609, 1105, 1, 629,
+        1001, 64, 1, 64, 1002, 64, 2, 64, 109, 7, 1208, -7, 30, 63, 1005, 63,
+        645, 1105, 1, 651, 4, 635, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -2,
+        21102, 46, 1, 9, 1008, 1016, 46, 63, 1005, 63, 677, 4, 657, 1001, 64, 1,
+        64, 1106, 0, 677, 1002, 64, 2, 64, 109, -2, 21108, 47, 48, 9, 1005, 1014,
+        697, 1001, 64, 1, 64, 1105, 1, 699, 4, 683, 1002, 64, 2, 64, 109, 14,
+        1205, 2, 713, 4, 705, 1105, 1, 717, 1001, 64, 1, 64, 1002, 64, 2, 64,
+        109, -7, 1206, 8, 735, 4, 723, 1001, 64, 1, 64, 1106, 0, 735, 1002, 64,
+        2, 64, 109, -18, 2101, 0, 6, 63, 1008, 63, 24, 63, 1005, 63, 759, 1001,
+        64, 1, 64, 1106, 0, 761, 4, 741, 1002, 64, 2, 64, 109, 29, 2105, 1,
+        1, 4, 767, 1106, 0, 779, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -5,
+        1206, 3, 791, 1106, 0, 797, 4, 785, 1001, 64, 1, 64, 1002, 64, 2, 64,
+        109, -12, 2107, 31, -1, 63, 1005, 63, 819, 4, 803, 1001, 64, 1, 64, 1105,
+        1, 819, 1002, 64, 2, 64, 109, 7, 1205, 7, 835, 1001, 64, 1, 64, 1105,
+        1, 837, 4, 825, 1002, 64, 2, 64, 109, -11, 1207, 7, 24, 63, 1005, 63,
+        853, 1106, 0, 859, 4, 843, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 4,
+        2108, 27, -6, 63, 1005, 63, 881, 4, 865, 1001, 64, 1, 64, 1106, 0, 881,
+        1002, 64, 2, 64, 109, 24, 2106, 0, -2, 4, 887, 1106, 0, 899, 1001, 64,
+        1, 64, 4, 64, 99, 21102, 27, 1, 1, 21101, 0, 913, 0, 1106, 0, 920,
+        21201, 1, 61934, 1, 204, 1, 99, 109, 3, 1207, -2, 3, 63, 1005, 63, 962,
+        21201, -2, -1, 1, 21101, 0, 940, 0, 1106, 0, 920, 21202, 1, 1, -1, 21201,
+        -2, -3, 1, 21101, 0, 955, 0, 1105, 1, 920, 22201, 1, -1, -2, 1105, 1,
+        966, 22102, 1, -2, -2, 109, -3, 2105, 1, 0,
+    ]),
+];
+
+static BLOCKS: &[(usize, usize, usize)] = &[
+    (0, 11, 3), (11, 25, 6), (25, 34, 3), (34, 41, 2), (41, 48, 2), (48, 53, 3), (53, 58, 3), (58, 63, 3),
+    (65, 190, 32), (190, 197, 2), (197, 199, 1), (199, 216, 5), (216, 219, 1), (219, 225, 2), (225, 242, 5), (242, 251, 3),
+    (251, 268, 5), (268, 271, 1), (271, 277, 2), (277, 294, 5), (294, 299, 2), (299, 303, 1), (303, 316, 4), (316, 321, 2),
+    (321, 325, 1), (325, 338, 4), (338, 345, 2), (345, 347, 1), (347, 364, 5), (364, 371, 2), (371, 373, 1), (373, 390, 5),
+    (390, 393, 1), (393, 399, 2), (399, 412, 4), (412, 417, 2), (417, 421, 1), (421, 438, 5), (438, 443, 2), (443, 447, 1),
+    (447, 464, 5), (464, 471, 2), (471, 473, 1), (473, 486, 4), (486, 489, 1), (489, 495, 2), (495, 508, 4), (508, 513, 2),
+    (513, 517, 1), (517, 530, 4), (530, 535, 2), (535, 539, 1), (539, 556, 5), (556, 561, 2), (561, 565, 1), (565, 572, 2),
+    (572, 575, 1), (575, 581, 2), (581, 594, 4), (594, 597, 1), (597, 603, 2), (603, 620, 5), (620, 625, 2), (625, 629, 1),
+    (629, 642, 4), (642, 645, 1), (645, 651, 2), (651, 668, 5), (668, 677, 3), (677, 690, 4), (690, 697, 2), (697, 699, 1),
+    (699, 708, 3), (708, 713, 2), (713, 717, 1), (717, 726, 3), (726, 735, 3), (735, 752, 5), (752, 759, 2), (759, 761, 1),
+    (761, 770, 3), (770, 775, 2), (775, 779, 1), (779, 788, 3), (788, 791, 1), (791, 797, 2), (797, 810, 4), (810, 819, 3),
+    (819, 828, 3), (828, 835, 2), (835, 837, 1), (837, 850, 4), (850, 853, 1), (853, 859, 2), (859, 872, 4), (872, 881, 3),
+    (881, 890, 3), (890, 895, 2), (895, 899, 1), (899, 902, 2), (902, 913, 3), (913, 920, 3), (920, 929, 3), (929, 940, 3),
+    (940, 955, 4), (955, 962, 2), (962, 966, 1), (966, 971, 2),
+];
+
+pub fn run(computer: &mut IntcodeComputer) -> Result<RunState, IntcodeError> {
+    let mut m = AotMachine::new(computer, CODE, BLOCKS);
+    let result = execute(&mut m);
+    m.finish(result)
+}
+
+fn execute(m: &mut AotMachine) -> Result<RunState, IntcodeError> {
+    loop {
+        match m.block() {
+            Some(0) => {
+                m.set_ip(0);
+                m.retire();
+                let v = m.mul(34463338, 34463338)?;
+                m.store(63, v);
+                m.set_ip(4);
+                m.retire();
+                let v = (m.load(63) < 34463338) as i64;
+                m.store(63, v);
+                m.set_ip(8);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(53);
+                    continue;
+                }
+                m.set_ip(11);
+            }
+            Some(11) => {
+                m.set_ip(11);
+                m.retire();
+                let v = m.add(3, 0)?;
+                m.store(1000, v);
+                m.set_ip(15);
+                m.retire();
+                m.adjust_relative_base(988)?;
+                m.set_ip(17);
+                m.retire();
+                m.adjust_relative_base(m.load_relative(12)?)?;
+                m.set_ip(19);
+                m.retire();
+                m.adjust_relative_base(m.load(1000))?;
+                m.set_ip(21);
+                m.retire();
+                m.adjust_relative_base(m.load_relative(6)?)?;
+                m.set_ip(23);
+                m.retire();
+                m.adjust_relative_base(m.load_relative(3)?)?;
+                m.set_ip(25);
+            }
+            Some(25) => {
+                m.set_ip(25);
+                m.retire();
+                let v = match m.input() { Some(v) => v, None => return Ok(m.needs_input()) };
+                if m.store_relative(0, v)? {
+                    m.set_ip(27);
+                    continue;
+                }
+                m.set_ip(27);
+                m.retire();
+                let v = (m.load(1000) == 1) as i64;
+                m.store(63, v);
+                m.set_ip(31);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(65);
+                    continue;
+                }
+                m.set_ip(34);
+            }
+            Some(34) => {
+                m.set_ip(34);
+                m.retire();
+                let v = (m.load(1000) == 2) as i64;
+                m.store(63, v);
+                m.set_ip(38);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(902);
+                    continue;
+                }
+                m.set_ip(41);
+            }
+            Some(41) => {
+                m.set_ip(41);
+                m.retire();
+                let v = (m.load(1000) == 0) as i64;
+                m.store(63, v);
+                m.set_ip(45);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(58);
+                    continue;
+                }
+                m.set_ip(48);
+            }
+            Some(48) => {
+                m.set_ip(48);
+                m.retire();
+                m.output(m.load(25));
+                m.set_ip(50);
+                m.retire();
+                m.output(0);
+                m.set_ip(52);
+                m.retire();
+                return Ok(m.halt());
+            }
+            Some(53) => {
+                m.set_ip(53);
+                m.retire();
+                m.output(m.load(0));
+                m.set_ip(55);
+                m.retire();
+                m.output(0);
+                m.set_ip(57);
+                m.retire();
+                return Ok(m.halt());
+            }
+            Some(58) => {
+                m.set_ip(58);
+                m.retire();
+                m.output(m.load(17));
+                m.set_ip(60);
+                m.retire();
+                m.output(0);
+                m.set_ip(62);
+                m.retire();
+                return Ok(m.halt());
+            }
+            Some(65) => {
+                m.set_ip(65);
+                m.retire();
+                let v = m.add(26, 0)?;
+                m.store(1015, v);
+                m.set_ip(69);
+                m.retire();
+                let v = m.add(29, 0)?;
+                m.store(1010, v);
+                m.set_ip(73);
+                m.retire();
+                let v = m.mul(1, 24)?;
+                m.store(1013, v);
+                m.set_ip(77);
+                m.retire();
+                let v = m.mul(1, 33)?;
+                m.store(1008, v);
+                m.set_ip(81);
+                m.retire();
+                let v = m.mul(36, 1)?;
+                m.store(1012, v);
+                m.set_ip(85);
+                m.retire();
+                let v = m.add(0, 572)?;
+                m.store(1023, v);
+                m.set_ip(89);
+                m.retire();
+                let v = m.add(35, 0)?;
+                m.store(1014, v);
+                m.set_ip(93);
+                m.retire();
+                let v = m.add(0, 38)?;
+                m.store(1019, v);
+                m.set_ip(97);
+                m.retire();
+                let v = m.mul(1, 30)?;
+                m.store(1006, v);
+                m.set_ip(101);
+                m.retire();
+                let v = m.add(0, 890)?;
+                m.store(1029, v);
+                m.set_ip(105);
+                m.retire();
+                let v = m.add(34, 0)?;
+                m.store(1011, v);
+                m.set_ip(109);
+                m.retire();
+                let v = m.add(28, 0)?;
+                m.store(1002, v);
+                m.set_ip(113);
+                m.retire();
+                let v = m.mul(1, 1)?;
+                m.store(1021, v);
+                m.set_ip(117);
+                m.retire();
+                let v = m.add(0, 37)?;
+                m.store(1001, v);
+                m.set_ip(121);
+                m.retire();
+                let v = m.add(0, 197)?;
+                m.store(1026, v);
+                m.set_ip(125);
+                m.retire();
+                let v = m.add(22, 0)?;
+                m.store(1017, v);
+                m.set_ip(129);
+                m.retire();
+                let v = m.mul(1, 895)?;
+                m.store(1028, v);
+                m.set_ip(133);
+                m.retire();
+                let v = m.add(0, 20)?;
+                m.store(1007, v);
+                m.set_ip(137);
+                m.retire();
+                let v = m.mul(21, 1)?;
+                m.store(1004, v);
+                m.set_ip(141);
+                m.retire();
+                let v = m.mul(1, 39)?;
+                m.store(1016, v);
+                m.set_ip(145);
+                m.retire();
+                let v = m.add(0, 0)?;
+                m.store(1020, v);
+                m.set_ip(149);
+                m.retire();
+                let v = m.mul(1, 190)?;
+                m.store(1027, v);
+                m.set_ip(153);
+                m.retire();
+                let v = m.add(0, 775)?;
+                m.store(1024, v);
+                m.set_ip(157);
+                m.retire();
+                let v = m.mul(31, 1)?;
+                m.store(1018, v);
+                m.set_ip(161);
+                m.retire();
+                let v = m.add(0, 23)?;
+                m.store(1003, v);
+                m.set_ip(165);
+                m.retire();
+                let v = m.add(0, 25)?;
+                m.store(1009, v);
+                m.set_ip(169);
+                m.retire();
+                let v = m.add(770, 0)?;
+                m.store(1025, v);
+                m.set_ip(173);
+                m.retire();
+                let v = m.add(0, 27)?;
+                m.store(1000, v);
+                m.set_ip(177);
+                m.retire();
+                let v = m.mul(1, 575)?;
+                m.store(1022, v);
+                m.set_ip(181);
+                m.retire();
+                let v = m.add(0, 32)?;
+                m.store(1005, v);
+                m.set_ip(185);
+                m.retire();
+                m.adjust_relative_base(27)?;
+                m.set_ip(187);
+                m.retire();
+                m.jump(m.load_relative(0)?)?;
+            }
+            Some(190) => {
+                m.set_ip(190);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(194);
+                m.retire();
+                m.set_ip(199);
+            }
+            Some(197) => {
+                m.set_ip(197);
+                m.retire();
+                m.output(m.load(187));
+                m.set_ip(199);
+            }
+            Some(199) => {
+                m.set_ip(199);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(203);
+                m.retire();
+                m.adjust_relative_base(-18)?;
+                m.set_ip(205);
+                m.retire();
+                let v = m.add(40, 0)?;
+                if m.store_relative(5, v)? {
+                    m.set_ip(209);
+                    continue;
+                }
+                m.set_ip(209);
+                m.retire();
+                let v = (m.load(1014) == 39) as i64;
+                m.store(63, v);
+                m.set_ip(213);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(219);
+                    continue;
+                }
+                m.set_ip(216);
+            }
+            Some(216) => {
+                m.set_ip(216);
+                m.retire();
+                m.set_ip(225);
+            }
+            Some(219) => {
+                m.set_ip(219);
+                m.retire();
+                m.output(m.load(205));
+                m.set_ip(221);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(225);
+            }
+            Some(225) => {
+                m.set_ip(225);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(229);
+                m.retire();
+                m.adjust_relative_base(-6)?;
+                m.set_ip(231);
+                m.retire();
+                let v = m.add(m.load_relative(-1)?, 0)?;
+                m.store(63, v);
+                m.set_ip(235);
+                m.retire();
+                let v = (m.load(63) == 28) as i64;
+                m.store(63, v);
+                m.set_ip(239);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(251);
+                    continue;
+                }
+                m.set_ip(242);
+            }
+            Some(242) => {
+                m.set_ip(242);
+                m.retire();
+                m.output(m.load(231));
+                m.set_ip(244);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(248);
+                m.retire();
+                m.set_ip(251);
+            }
+            Some(251) => {
+                m.set_ip(251);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(255);
+                m.retire();
+                m.adjust_relative_base(5)?;
+                m.set_ip(257);
+                m.retire();
+                let v = m.mul(41, 1)?;
+                if m.store_relative(3, v)? {
+                    m.set_ip(261);
+                    continue;
+                }
+                m.set_ip(261);
+                m.retire();
+                let v = (m.load(1011) == 38) as i64;
+                m.store(63, v);
+                m.set_ip(265);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(271);
+                    continue;
+                }
+                m.set_ip(268);
+            }
+            Some(268) => {
+                m.set_ip(268);
+                m.retire();
+                m.set_ip(277);
+            }
+            Some(271) => {
+                m.set_ip(271);
+                m.retire();
+                m.output(m.load(257));
+                m.set_ip(273);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(277);
+            }
+            Some(277) => {
+                m.set_ip(277);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(281);
+                m.retire();
+                m.adjust_relative_base(-7)?;
+                m.set_ip(283);
+                m.retire();
+                let v = m.mul(1, m.load_relative(1)?)?;
+                m.store(63, v);
+                m.set_ip(287);
+                m.retire();
+                let v = (m.load(63) == 28) as i64;
+                m.store(63, v);
+                m.set_ip(291);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(299);
+                    continue;
+                }
+                m.set_ip(294);
+            }
+            Some(294) => {
+                m.set_ip(294);
+                m.retire();
+                m.output(m.load(283));
+                m.set_ip(296);
+                m.retire();
+                m.set_ip(303);
+            }
+            Some(299) => {
+                m.set_ip(299);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(303);
+            }
+            Some(303) => {
+                m.set_ip(303);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(307);
+                m.retire();
+                m.adjust_relative_base(-7)?;
+                m.set_ip(309);
+                m.retire();
+                let v = (m.load_relative(10)? < 22) as i64;
+                m.store(63, v);
+                m.set_ip(313);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(321);
+                    continue;
+                }
+                m.set_ip(316);
+            }
+            Some(316) => {
+                m.set_ip(316);
+                m.retire();
+                m.output(m.load(309));
+                m.set_ip(318);
+                m.retire();
+                m.set_ip(325);
+            }
+            Some(321) => {
+                m.set_ip(321);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(325);
+            }
+            Some(325) => {
+                m.set_ip(325);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(329);
+                m.retire();
+                m.adjust_relative_base(16)?;
+                m.set_ip(331);
+                m.retire();
+                let v = (31 < m.load_relative(-4)?) as i64;
+                m.store(63, v);
+                m.set_ip(335);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(345);
+                    continue;
+                }
+                m.set_ip(338);
+            }
+            Some(338) => {
+                m.set_ip(338);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(342);
+                m.retire();
+                m.set_ip(347);
+            }
+            Some(345) => {
+                m.set_ip(345);
+                m.retire();
+                m.output(m.load(331));
+                m.set_ip(347);
+            }
+            Some(347) => {
+                m.set_ip(347);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(351);
+                m.retire();
+                m.adjust_relative_base(-9)?;
+                m.set_ip(353);
+                m.retire();
+                let v = m.add(m.load_relative(3)?, 0)?;
+                m.store(63, v);
+                m.set_ip(357);
+                m.retire();
+                let v = (m.load(63) == 18) as i64;
+                m.store(63, v);
+                m.set_ip(361);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(371);
+                    continue;
+                }
+                m.set_ip(364);
+            }
+            Some(364) => {
+                m.set_ip(364);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(368);
+                m.retire();
+                m.set_ip(373);
+            }
+            Some(371) => {
+                m.set_ip(371);
+                m.retire();
+                m.output(m.load(353));
+                m.set_ip(373);
+            }
+            Some(373) => {
+                m.set_ip(373);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(377);
+                m.retire();
+                m.adjust_relative_base(7)?;
+                m.set_ip(379);
+                m.retire();
+                let v = m.mul(m.load_relative(-7)?, 1)?;
+                m.store(63, v);
+                m.set_ip(383);
+                m.retire();
+                let v = (m.load(63) == 40) as i64;
+                m.store(63, v);
+                m.set_ip(387);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(393);
+                    continue;
+                }
+                m.set_ip(390);
+            }
+            Some(390) => {
+                m.set_ip(390);
+                m.retire();
+                m.set_ip(399);
+            }
+            Some(393) => {
+                m.set_ip(393);
+                m.retire();
+                m.output(m.load(379));
+                m.set_ip(395);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(399);
+            }
+            Some(399) => {
+                m.set_ip(399);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(403);
+                m.retire();
+                m.adjust_relative_base(-5)?;
+                m.set_ip(405);
+                m.retire();
+                let v = (m.load_relative(5)? == 33) as i64;
+                m.store(63, v);
+                m.set_ip(409);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(417);
+                    continue;
+                }
+                m.set_ip(412);
+            }
+            Some(412) => {
+                m.set_ip(412);
+                m.retire();
+                m.output(m.load(405));
+                m.set_ip(414);
+                m.retire();
+                m.set_ip(421);
+            }
+            Some(417) => {
+                m.set_ip(417);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(421);
+            }
+            Some(421) => {
+                m.set_ip(421);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(425);
+                m.retire();
+                m.adjust_relative_base(1)?;
+                m.set_ip(427);
+                m.retire();
+                let v = m.mul(m.load_relative(2)?, 1)?;
+                m.store(63, v);
+                m.set_ip(431);
+                m.retire();
+                let v = (m.load(63) == 30) as i64;
+                m.store(63, v);
+                m.set_ip(435);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(443);
+                    continue;
+                }
+                m.set_ip(438);
+            }
+            Some(438) => {
+                m.set_ip(438);
+                m.retire();
+                m.output(m.load(427));
+                m.set_ip(440);
+                m.retire();
+                m.set_ip(447);
+            }
+            Some(443) => {
+                m.set_ip(443);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(447);
+            }
+            Some(447) => {
+                m.set_ip(447);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(451);
+                m.retire();
+                m.adjust_relative_base(-7)?;
+                m.set_ip(453);
+                m.retire();
+                let v = m.mul(1, m.load_relative(10)?)?;
+                m.store(63, v);
+                m.set_ip(457);
+                m.retire();
+                let v = (m.load(63) == 19) as i64;
+                m.store(63, v);
+                m.set_ip(461);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(471);
+                    continue;
+                }
+                m.set_ip(464);
+            }
+            Some(464) => {
+                m.set_ip(464);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(468);
+                m.retire();
+                m.set_ip(473);
+            }
+            Some(471) => {
+                m.set_ip(471);
+                m.retire();
+                m.output(m.load(453));
+                m.set_ip(473);
+            }
+            Some(473) => {
+                m.set_ip(473);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(477);
+                m.retire();
+                m.adjust_relative_base(6)?;
+                m.set_ip(479);
+                m.retire();
+                let v = (21 == m.load_relative(0)?) as i64;
+                m.store(63, v);
+                m.set_ip(483);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(489);
+                    continue;
+                }
+                m.set_ip(486);
+            }
+            Some(486) => {
+                m.set_ip(486);
+                m.retire();
+                m.set_ip(495);
+            }
+            Some(489) => {
+                m.set_ip(489);
+                m.retire();
+                m.output(m.load(479));
+                m.set_ip(491);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(495);
+            }
+            Some(495) => {
+                m.set_ip(495);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(499);
+                m.retire();
+                m.adjust_relative_base(9)?;
+                m.set_ip(501);
+                m.retire();
+                let v = (42 == 42) as i64;
+                if m.store_relative(0, v)? {
+                    m.set_ip(505);
+                    continue;
+                }
+                m.set_ip(505);
+                m.retire();
+                if m.load(1012) != 0 {
+                    m.set_ip(513);
+                    continue;
+                }
+                m.set_ip(508);
+            }
+            Some(508) => {
+                m.set_ip(508);
+                m.retire();
+                m.output(m.load(501));
+                m.set_ip(510);
+                m.retire();
+                m.set_ip(517);
+            }
+            Some(513) => {
+                m.set_ip(513);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(517);
+            }
+            Some(517) => {
+                m.set_ip(517);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(521);
+                m.retire();
+                m.adjust_relative_base(7)?;
+                m.set_ip(523);
+                m.retire();
+                let v = (43 < 44) as i64;
+                if m.store_relative(-1, v)? {
+                    m.set_ip(527);
+                    continue;
+                }
+                m.set_ip(527);
+                m.retire();
+                if m.load(1018) != 0 {
+                    m.set_ip(535);
+                    continue;
+                }
+                m.set_ip(530);
+            }
+            Some(530) => {
+                m.set_ip(530);
+                m.retire();
+                m.output(m.load(523));
+                m.set_ip(532);
+                m.retire();
+                m.set_ip(539);
+            }
+            Some(535) => {
+                m.set_ip(535);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(539);
+            }
+            Some(539) => {
+                m.set_ip(539);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(543);
+                m.retire();
+                m.adjust_relative_base(-5)?;
+                m.set_ip(545);
+                m.retire();
+                let v = m.add(44, 0)?;
+                if m.store_relative(2, v)? {
+                    m.set_ip(549);
+                    continue;
+                }
+                m.set_ip(549);
+                m.retire();
+                let v = (m.load(1016) == 44) as i64;
+                m.store(63, v);
+                m.set_ip(553);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(561);
+                    continue;
+                }
+                m.set_ip(556);
+            }
+            Some(556) => {
+                m.set_ip(556);
+                m.retire();
+                m.output(m.load(545));
+                m.set_ip(558);
+                m.retire();
+                m.set_ip(565);
+            }
+            Some(561) => {
+                m.set_ip(561);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(565);
+            }
+            Some(565) => {
+                m.set_ip(565);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(569);
+                m.retire();
+                m.jump(m.load_relative(9)?)?;
+            }
+            Some(572) => {
+                m.set_ip(572);
+                m.retire();
+                m.set_ip(581);
+            }
+            Some(575) => {
+                m.set_ip(575);
+                m.retire();
+                m.output(m.load(569));
+                m.set_ip(577);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(581);
+            }
+            Some(581) => {
+                m.set_ip(581);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(585);
+                m.retire();
+                m.adjust_relative_base(13)?;
+                m.set_ip(587);
+                m.retire();
+                let v = (45 < 44) as i64;
+                if m.store_relative(-9, v)? {
+                    m.set_ip(591);
+                    continue;
+                }
+                m.set_ip(591);
+                m.retire();
+                if m.load(1018) != 0 {
+                    m.set_ip(597);
+                    continue;
+                }
+                m.set_ip(594);
+            }
+            Some(594) => {
+                m.set_ip(594);
+                m.retire();
+                m.set_ip(603);
+            }
+            Some(597) => {
+                m.set_ip(597);
+                m.retire();
+                m.output(m.load(587));
+                m.set_ip(599);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(603);
+            }
+            Some(603) => {
+                m.set_ip(603);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(607);
+                m.retire();
+                m.adjust_relative_base(-25)?;
+                m.set_ip(609);
+                m.retire();
+                let v = m.add(0, m.load_relative(3)?)?;
+                m.store(63, v);
+                m.set_ip(613);
+                m.retire();
+                let v = (m.load(63) == 32) as i64;
+                m.store(63, v);
+                m.set_ip(617);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(625);
+                    continue;
+                }
+                m.set_ip(620);
+            }
+            Some(620) => {
+                m.set_ip(620);
+                m.retire();
+                m.output(m.load(609));
+                m.set_ip(622);
+                m.retire();
+                m.set_ip(629);
+            }
+            Some(625) => {
+                m.set_ip(625);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(629);
+            }
+            Some(629) => {
+                m.set_ip(629);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(633);
+                m.retire();
+                m.adjust_relative_base(7)?;
+                m.set_ip(635);
+                m.retire();
+                let v = (m.load_relative(-7)? == 30) as i64;
+                m.store(63, v);
+                m.set_ip(639);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(645);
+                    continue;
+                }
+                m.set_ip(642);
+            }
+            Some(642) => {
+                m.set_ip(642);
+                m.retire();
+                m.set_ip(651);
+            }
+            Some(645) => {
+                m.set_ip(645);
+                m.retire();
+                m.output(m.load(635));
+                m.set_ip(647);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(651);
+            }
+            Some(651) => {
+                m.set_ip(651);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(655);
+                m.retire();
+                m.adjust_relative_base(-2)?;
+                m.set_ip(657);
+                m.retire();
+                let v = m.mul(46, 1)?;
+                if m.store_relative(9, v)? {
+                    m.set_ip(661);
+                    continue;
+                }
+                m.set_ip(661);
+                m.retire();
+                let v = (m.load(1016) == 46) as i64;
+                m.store(63, v);
+                m.set_ip(665);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(677);
+                    continue;
+                }
+                m.set_ip(668);
+            }
+            Some(668) => {
+                m.set_ip(668);
+                m.retire();
+                m.output(m.load(657));
+                m.set_ip(670);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(674);
+                m.retire();
+                m.set_ip(677);
+            }
+            Some(677) => {
+                m.set_ip(677);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(681);
+                m.retire();
+                m.adjust_relative_base(-2)?;
+                m.set_ip(683);
+                m.retire();
+                let v = (47 == 48) as i64;
+                if m.store_relative(9, v)? {
+                    m.set_ip(687);
+                    continue;
+                }
+                m.set_ip(687);
+                m.retire();
+                if m.load(1014) != 0 {
+                    m.set_ip(697);
+                    continue;
+                }
+                m.set_ip(690);
+            }
+            Some(690) => {
+                m.set_ip(690);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(694);
+                m.retire();
+                m.set_ip(699);
+            }
+            Some(697) => {
+                m.set_ip(697);
+                m.retire();
+                m.output(m.load(683));
+                m.set_ip(699);
+            }
+            Some(699) => {
+                m.set_ip(699);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(703);
+                m.retire();
+                m.adjust_relative_base(14)?;
+                m.set_ip(705);
+                m.retire();
+                if m.load_relative(2)? != 0 {
+                    m.set_ip(713);
+                    continue;
+                }
+                m.set_ip(708);
+            }
+            Some(708) => {
+                m.set_ip(708);
+                m.retire();
+                m.output(m.load(705));
+                m.set_ip(710);
+                m.retire();
+                m.set_ip(717);
+            }
+            Some(713) => {
+                m.set_ip(713);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(717);
+            }
+            Some(717) => {
+                m.set_ip(717);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(721);
+                m.retire();
+                m.adjust_relative_base(-7)?;
+                m.set_ip(723);
+                m.retire();
+                if m.load_relative(8)? == 0 {
+                    m.set_ip(735);
+                    continue;
+                }
+                m.set_ip(726);
+            }
+            Some(726) => {
+                m.set_ip(726);
+                m.retire();
+                m.output(m.load(723));
+                m.set_ip(728);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(732);
+                m.retire();
+                m.set_ip(735);
+            }
+            Some(735) => {
+                m.set_ip(735);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(739);
+                m.retire();
+                m.adjust_relative_base(-18)?;
+                m.set_ip(741);
+                m.retire();
+                let v = m.add(0, m.load_relative(6)?)?;
+                m.store(63, v);
+                m.set_ip(745);
+                m.retire();
+                let v = (m.load(63) == 24) as i64;
+                m.store(63, v);
+                m.set_ip(749);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(759);
+                    continue;
+                }
+                m.set_ip(752);
+            }
+            Some(752) => {
+                m.set_ip(752);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(756);
+                m.retire();
+                m.set_ip(761);
+            }
+            Some(759) => {
+                m.set_ip(759);
+                m.retire();
+                m.output(m.load(741));
+                m.set_ip(761);
+            }
+            Some(761) => {
+                m.set_ip(761);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(765);
+                m.retire();
+                m.adjust_relative_base(29)?;
+                m.set_ip(767);
+                m.retire();
+                m.jump(m.load_relative(1)?)?;
+            }
+            Some(770) => {
+                m.set_ip(770);
+                m.retire();
+                m.output(m.load(767));
+                m.set_ip(772);
+                m.retire();
+                m.set_ip(779);
+            }
+            Some(775) => {
+                m.set_ip(775);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(779);
+            }
+            Some(779) => {
+                m.set_ip(779);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(783);
+                m.retire();
+                m.adjust_relative_base(-5)?;
+                m.set_ip(785);
+                m.retire();
+                if m.load_relative(3)? == 0 {
+                    m.set_ip(791);
+                    continue;
+                }
+                m.set_ip(788);
+            }
+            Some(788) => {
+                m.set_ip(788);
+                m.retire();
+                m.set_ip(797);
+            }
+            Some(791) => {
+                m.set_ip(791);
+                m.retire();
+                m.output(m.load(785));
+                m.set_ip(793);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(797);
+            }
+            Some(797) => {
+                m.set_ip(797);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(801);
+                m.retire();
+                m.adjust_relative_base(-12)?;
+                m.set_ip(803);
+                m.retire();
+                let v = (31 < m.load_relative(-1)?) as i64;
+                m.store(63, v);
+                m.set_ip(807);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(819);
+                    continue;
+                }
+                m.set_ip(810);
+            }
+            Some(810) => {
+                m.set_ip(810);
+                m.retire();
+                m.output(m.load(803));
+                m.set_ip(812);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(816);
+                m.retire();
+                m.set_ip(819);
+            }
+            Some(819) => {
+                m.set_ip(819);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(823);
+                m.retire();
+                m.adjust_relative_base(7)?;
+                m.set_ip(825);
+                m.retire();
+                if m.load_relative(7)? != 0 {
+                    m.set_ip(835);
+                    continue;
+                }
+                m.set_ip(828);
+            }
+            Some(828) => {
+                m.set_ip(828);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(832);
+                m.retire();
+                m.set_ip(837);
+            }
+            Some(835) => {
+                m.set_ip(835);
+                m.retire();
+                m.output(m.load(825));
+                m.set_ip(837);
+            }
+            Some(837) => {
+                m.set_ip(837);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(841);
+                m.retire();
+                m.adjust_relative_base(-11)?;
+                m.set_ip(843);
+                m.retire();
+                let v = (m.load_relative(7)? < 24) as i64;
+                m.store(63, v);
+                m.set_ip(847);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(853);
+                    continue;
+                }
+                m.set_ip(850);
+            }
+            Some(850) => {
+                m.set_ip(850);
+                m.retire();
+                m.set_ip(859);
+            }
+            Some(853) => {
+                m.set_ip(853);
+                m.retire();
+                m.output(m.load(843));
+                m.set_ip(855);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(859);
+            }
+            Some(859) => {
+                m.set_ip(859);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(863);
+                m.retire();
+                m.adjust_relative_base(4)?;
+                m.set_ip(865);
+                m.retire();
+                let v = (27 == m.load_relative(-6)?) as i64;
+                m.store(63, v);
+                m.set_ip(869);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(881);
+                    continue;
+                }
+                m.set_ip(872);
+            }
+            Some(872) => {
+                m.set_ip(872);
+                m.retire();
+                m.output(m.load(865));
+                m.set_ip(874);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(878);
+                m.retire();
+                m.set_ip(881);
+            }
+            Some(881) => {
+                m.set_ip(881);
+                m.retire();
+                let v = m.mul(m.load(64), 2)?;
+                m.store(64, v);
+                m.set_ip(885);
+                m.retire();
+                m.adjust_relative_base(24)?;
+                m.set_ip(887);
+                m.retire();
+                m.jump(m.load_relative(-2)?)?;
+            }
+            Some(890) => {
+                m.set_ip(890);
+                m.retire();
+                m.output(m.load(887));
+                m.set_ip(892);
+                m.retire();
+                m.set_ip(899);
+            }
+            Some(895) => {
+                m.set_ip(895);
+                m.retire();
+                let v = m.add(m.load(64), 1)?;
+                m.store(64, v);
+                m.set_ip(899);
+            }
+            Some(899) => {
+                m.set_ip(899);
+                m.retire();
+                m.output(m.load(64));
+                m.set_ip(901);
+                m.retire();
+                return Ok(m.halt());
+            }
+            Some(902) => {
+                m.set_ip(902);
+                m.retire();
+                let v = m.mul(27, 1)?;
+                if m.store_relative(1, v)? {
+                    m.set_ip(906);
+                    continue;
+                }
+                m.set_ip(906);
+                m.retire();
+                let v = m.add(0, 913)?;
+                if m.store_relative(0, v)? {
+                    m.set_ip(910);
+                    continue;
+                }
+                m.set_ip(910);
+                m.retire();
+                m.set_ip(920);
+            }
+            Some(913) => {
+                m.set_ip(913);
+                m.retire();
+                let v = m.add(m.load_relative(1)?, 61934)?;
+                if m.store_relative(1, v)? {
+                    m.set_ip(917);
+                    continue;
+                }
+                m.set_ip(917);
+                m.retire();
+                m.output(m.load_relative(1)?);
+                m.set_ip(919);
+                m.retire();
+                return Ok(m.halt());
+            }
+            Some(920) => {
+                m.set_ip(920);
+                m.retire();
+                m.adjust_relative_base(3)?;
+                m.set_ip(922);
+                m.retire();
+                let v = (m.load_relative(-2)? < 3) as i64;
+                m.store(63, v);
+                m.set_ip(926);
+                m.retire();
+                if m.load(63) != 0 {
+                    m.set_ip(962);
+                    continue;
+                }
+                m.set_ip(929);
+            }
+            Some(929) => {
+                m.set_ip(929);
+                m.retire();
+                let v = m.add(m.load_relative(-2)?, -1)?;
+                if m.store_relative(1, v)? {
+                    m.set_ip(933);
+                    continue;
+                }
+                m.set_ip(933);
+                m.retire();
+                let v = m.add(0, 940)?;
+                if m.store_relative(0, v)? {
+                    m.set_ip(937);
+                    continue;
+                }
+                m.set_ip(937);
+                m.retire();
+                m.set_ip(920);
+            }
+            Some(940) => {
+                m.set_ip(940);
+                m.retire();
+                let v = m.mul(m.load_relative(1)?, 1)?;
+                if m.store_relative(-1, v)? {
+                    m.set_ip(944);
+                    continue;
+                }
+                m.set_ip(944);
+                m.retire();
+                let v = m.add(m.load_relative(-2)?, -3)?;
+                if m.store_relative(1, v)? {
+                    m.set_ip(948);
+                    continue;
+                }
+                m.set_ip(948);
+                m.retire();
+                let v = m.add(0, 955)?;
+                if m.store_relative(0, v)? {
+                    m.set_ip(952);
+                    continue;
+                }
+                m.set_ip(952);
+                m.retire();
+                m.set_ip(920);
+            }
+            Some(955) => {
+                m.set_ip(955);
+                m.retire();
+                let v = m.add(m.load_relative(1)?, m.load_relative(-1)?)?;
+                if m.store_relative(-2, v)? {
+                    m.set_ip(959);
+                    continue;
+                }
+                m.set_ip(959);
+                m.retire();
+                m.set_ip(966);
+            }
+            Some(962) => {
+                m.set_ip(962);
+                m.retire();
+                let v = m.mul(1, m.load_relative(-2)?)?;
+                if m.store_relative(-2, v)? {
+                    m.set_ip(966);
+                    continue;
+                }
+                m.set_ip(966);
+            }
+            Some(966) => {
+                m.set_ip(966);
+                m.retire();
+                m.adjust_relative_base(-3)?;
+                m.set_ip(968);
+                m.retire();
+                m.jump(m.load_relative(0)?)?;
+            }
+            _ => {
+                if let Some(state) = m.interpret()? {
+                    return Ok(state);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,507 @@
+//! Differential testing of `IntcodeComputer` against a minimal reference
+//! interpreter, over randomly generated programs.
+//!
+//! Set `FUZZ_CASES` and `FUZZ_SEED` to run more or different cases.
+
+use advent_of_code_2019::{Engine, IntcodeComputer, IntcodeError, PagedMemory, RunState};
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Instructions executed before a run is cut short.
+const STEP_LIMIT: u64 = 500;
+
+/// A xorshift64* generator, so that failures reproduce from their seed.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Rng(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn range(&mut self, start: i64, end: i64) -> i64 {
+        start + self.below((end - start) as u64) as i64
+    }
+
+    fn chance(&mut self, one_in: u64) -> bool {
+        self.below(one_in) == 0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Case {
+    program: Vec<i64>,
+    input: Vec<i64>,
+}
+
+impl fmt::Display for Case {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let join = |values: &[i64]| {
+            values
+                .iter()
+                .map(i64::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        write!(
+            f,
+            "program: {}\ninput: {}",
+            join(&self.program),
+            join(&self.input)
+        )
+    }
+}
+
+fn extreme(rng: &mut Rng) -> i64 {
+    const EXTREMES: [i64; 5] = [i64::MIN, i64::MAX, i64::MAX / 2 + 1, -(1 << 40), 1 << 40];
+    EXTREMES[rng.below(EXTREMES.len() as u64) as usize]
+}
+
+/// A small value, or occasionally an extreme one.
+fn value(rng: &mut Rng) -> i64 {
+    if rng.chance(20) {
+        extreme(rng)
+    } else {
+        rng.range(-10, 100)
+    }
+}
+
+/// An address close to a program bounded by `bound`, or occasionally a
+/// distant one.
+fn address(rng: &mut Rng, bound: i64) -> i64 {
+    if rng.chance(20) {
+        rng.range(1 << 20, 1 << 50)
+    } else {
+        rng.range(0, bound)
+    }
+}
+
+/// Generates a program made mostly of valid instructions, whose position
+/// operands and jump targets mostly stay close to the program itself. Some
+/// addresses are distant and some values extreme, so that memory grows far
+/// and the relative base can overflow.
+fn generate(rng: &mut Rng) -> Case {
+    const OPCODES: [i64; 10] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 99];
+
+    let instructions = rng.range(1, 25) as usize;
+    let bound = 4 * instructions as i64 + 8;
+    let mut program = Vec::new();
+    for _ in 0..instructions {
+        if rng.chance(50) {
+            program.push(rng.range(-10, 100_000));
+            continue;
+        }
+
+        let opcode = OPCODES[rng.below(OPCODES.len() as u64) as usize];
+        let (arity, destination) = match opcode {
+            1 | 2 | 7 | 8 => (3, Some(2)),
+            3 => (1, Some(0)),
+            4 | 9 => (1, None),
+            5 | 6 => (2, None),
+            _ => (0, None),
+        };
+        let mut word = opcode;
+        let mut params = Vec::new();
+        for idx in 0..arity {
+            let mode = match Some(idx) == destination {
+                true if rng.chance(50) => 1,
+                true => [0, 2][rng.below(2) as usize],
+                false => rng.below(3) as i64,
+            };
+            word += mode * 10i64.pow(idx as u32 + 2);
+            let jump_target = (opcode == 5 || opcode == 6) && idx == 1;
+            params.push(match mode {
+                0 => address(rng, bound),
+                1 if jump_target => rng.range(0, bound),
+                1 if opcode == 9 && rng.chance(5) => extreme(rng),
+                1 if opcode == 9 => rng.range(-8, 16),
+                1 => value(rng),
+                _ => rng.range(-8, 8),
+            });
+        }
+        program.push(word);
+        program.extend(params);
+    }
+    for _ in 0..8 {
+        program.push(value(rng));
+    }
+
+    let input = (0..rng.below(4)).map(|_| value(rng)).collect();
+    Case { program, input }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Fault {
+    InvalidInstruction,
+    NegativeAddress(i64),
+    WriteToImmediate,
+    Overflow,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Status {
+    Halted,
+    NeedsInput,
+    /// Ran out of steps.
+    Limit,
+    Fault(Fault),
+}
+
+/// How a run ended, comparable between implementations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Outcome {
+    status: Status,
+    ip: usize,
+    relative_base: i64,
+    output: Vec<i64>,
+    /// Non-zero cells by address.
+    memory: Vec<(usize, i64)>,
+}
+
+/// A deliberately naive interpreter, written separately from
+/// `IntcodeComputer` and without sharing any of its code.
+struct Reference {
+    memory: BTreeMap<usize, i64>,
+    input: Vec<i64>,
+    output: Vec<i64>,
+    ip: usize,
+    relative_base: i64,
+    modes: [i64; 3],
+}
+
+impl Reference {
+    fn read(&self, address: usize) -> i64 {
+        self.memory.get(&address).copied().unwrap_or(0)
+    }
+
+    /// The address parameter `idx` refers to, or `None` if it's immediate.
+    fn address(&self, idx: usize) -> Result<Option<usize>, Status> {
+        let param = self.read(self.ip + 1 + idx);
+        let address = match self.modes[idx] {
+            0 => param,
+            1 => return Ok(None),
+            _ => self
+                .relative_base
+                .checked_add(param)
+                .ok_or(Status::Fault(Fault::Overflow))?,
+        };
+        if address < 0 {
+            return Err(Status::Fault(Fault::NegativeAddress(address)));
+        }
+        Ok(Some(address as usize))
+    }
+
+    fn load(&self, idx: usize) -> Result<i64, Status> {
+        Ok(match self.address(idx)? {
+            Some(address) => self.read(address),
+            None => self.read(self.ip + 1 + idx),
+        })
+    }
+
+    fn store(&mut self, idx: usize, value: i64) -> Result<(), Status> {
+        let address = self
+            .address(idx)?
+            .ok_or(Status::Fault(Fault::WriteToImmediate))?;
+        if value == 0 {
+            self.memory.remove(&address);
+        } else {
+            self.memory.insert(address, value);
+        }
+        Ok(())
+    }
+
+    /// Executes one instruction, failing with the status to stop in.
+    fn step(&mut self) -> Result<(), Status> {
+        let invalid = Status::Fault(Fault::InvalidInstruction);
+        let word = self.read(self.ip);
+        if !(0..100_000).contains(&word) {
+            return Err(invalid);
+        }
+        let opcode = word % 100;
+        self.modes = [word / 100 % 10, word / 1000 % 10, word / 10000 % 10];
+        let arity = match opcode {
+            1 | 2 | 7 | 8 => 3,
+            5 | 6 => 2,
+            3 | 4 | 9 => 1,
+            99 => 0,
+            _ => return Err(invalid),
+        };
+        if self.modes.iter().any(|&mode| mode > 2) || self.modes[arity..].iter().any(|&m| m != 0) {
+            return Err(invalid);
+        }
+
+        let mut next = self.ip + 1 + arity;
+        match opcode {
+            1 | 2 | 7 | 8 => {
+                let (a, b) = (self.load(0)?, self.load(1)?);
+                let value = match opcode {
+                    1 => a.wrapping_add(b),
+                    2 => a.wrapping_mul(b),
+                    7 => (a < b) as i64,
+                    _ => (a == b) as i64,
+                };
+                self.store(2, value)?;
+            }
+            3 => {
+                if self.input.is_empty() {
+                    return Err(Status::NeedsInput);
+                }
+                let value = self.input.remove(0);
+                self.store(0, value)?;
+            }
+            4 => {
+                let value = self.load(0)?;
+                self.output.push(value);
+            }
+            5 | 6 => {
+                if (self.load(0)? != 0) == (opcode == 5) {
+                    let target = self.load(1)?;
+                    if target < 0 {
+                        return Err(Status::Fault(Fault::NegativeAddress(target)));
+                    }
+                    next = target as usize;
+                }
+            }
+            9 => self.relative_base = self.relative_base.wrapping_add(self.load(0)?),
+            _ => {
+                self.ip += 1;
+                return Err(Status::Halted);
+            }
+        }
+        self.ip = next;
+        Ok(())
+    }
+}
+
+fn reference(case: &Case) -> Outcome {
+    let mut machine = Reference {
+        memory: case
+            .program
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|&(_, value)| value != 0)
+            .collect(),
+        input: case.input.clone(),
+        output: Vec::new(),
+        ip: 0,
+        relative_base: 0,
+        modes: [0; 3],
+    };
+    let status = (0..STEP_LIMIT)
+        .find_map(|_| machine.step().err())
+        .unwrap_or(Status::Limit);
+
+    Outcome {
+        status,
+        ip: machine.ip,
+        relative_base: machine.relative_base,
+        output: machine.output,
+        memory: machine.memory.into_iter().collect(),
+    }
+}
+
+fn run(case: &Case, mut computer: IntcodeComputer) -> Outcome {
+    for &value in &case.input {
+        computer.input(value);
+    }
+    computer.set_budget(Some(STEP_LIMIT));
+    let (status, ip) = match computer.run() {
+        Ok(RunState::Halted) => (Status::Halted, computer.ip()),
+        Ok(RunState::NeedsInput) => (Status::NeedsInput, computer.ip()),
+        Ok(RunState::BudgetExhausted) => (Status::Limit, computer.ip()),
+        Ok(state) => panic!("unexpected state {:?}", state),
+        Err(err) => {
+            let fault = match err {
+                IntcodeError::InvalidInstruction { .. } => Fault::InvalidInstruction,
+                IntcodeError::NegativeAddress { address, .. } => Fault::NegativeAddress(address),
+                IntcodeError::WriteToImmediate { .. } => Fault::WriteToImmediate,
+                IntcodeError::Overflow { .. } => Fault::Overflow,
+                IntcodeError::Handler { .. } => panic!("unexpected error {}", err),
+            };
+            (Status::Fault(fault), err.context().ip)
+        }
+    };
+
+    let memory = computer
+        .memory()
+        .segments()
+        .into_iter()
+        .flat_map(|(start, cells)| {
+            let cells = cells.into_iter().enumerate();
+            cells.map(move |(idx, value)| (start + idx, value))
+        })
+        .filter(|&(_, value)| value != 0)
+        .collect();
+    Outcome {
+        status,
+        ip,
+        relative_base: computer.relative_base(),
+        output: computer.output().to_vec(),
+        memory,
+    }
+}
+
+/// Describes the first configuration of `IntcodeComputer` which disagrees
+/// with the reference interpreter or panics, if any.
+fn divergence(case: &Case) -> Option<String> {
+    let expected = reference(case);
+    type Constructor = fn(&[i64]) -> IntcodeComputer;
+    let configurations: [(&str, Constructor); 4] = [
+        ("interpreter", IntcodeComputer::new),
+        ("cached", |program| {
+            IntcodeComputer::builder()
+                .engine(Engine::Cached)
+                .build(program)
+        }),
+        ("paged", |program| {
+            IntcodeComputer::with_memory(program, PagedMemory::default())
+        }),
+        ("shared", |program| IntcodeComputer::shared(program.into())),
+    ];
+    configurations.iter().find_map(|(name, new)| {
+        match panic::catch_unwind(AssertUnwindSafe(|| run(case, new(&case.program)))) {
+            Ok(actual) if actual == expected => None,
+            Ok(actual) => Some(format!(
+                "{} diverged\nexpected: {:?}\nactual: {:?}",
+                name, expected, actual
+            )),
+            Err(_) => Some(format!("{} panicked\nexpected: {:?}", name, expected)),
+        }
+    })
+}
+
+/// Shrinks `case` while `interesting` still holds, by removing runs of
+/// words and inputs and by zeroing words.
+fn minimize(mut case: Case, interesting: impl Fn(&Case) -> bool) -> Case {
+    loop {
+        let before = case.clone();
+
+        for chunk in (1..=case.program.len()).rev() {
+            let mut start = 0;
+            while start < case.program.len() {
+                let mut candidate = case.clone();
+                let end = (start + chunk).min(candidate.program.len());
+                candidate.program.drain(start..end);
+                if interesting(&candidate) {
+                    case = candidate;
+                } else if chunk <= 4 {
+                    // Slide instruction sized chunks one word at a time, as
+                    // instructions needn't be aligned to the chunk size
+                    start += 1;
+                } else {
+                    start += chunk;
+                }
+            }
+        }
+
+        for idx in 0..case.program.len() {
+            if case.program[idx] != 0 {
+                let mut candidate = case.clone();
+                candidate.program[idx] = 0;
+                if interesting(&candidate) {
+                    case = candidate;
+                }
+            }
+        }
+
+        let mut idx = 0;
+        while idx < case.input.len() {
+            let mut candidate = case.clone();
+            candidate.input.remove(idx);
+            if interesting(&candidate) {
+                case = candidate;
+            } else {
+                idx += 1;
+            }
+        }
+
+        if case == before {
+            return case;
+        }
+    }
+}
+
+fn env_or(name: &str, default: u64) -> u64 {
+    env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+#[test]
+fn differential() {
+    let seed = env_or("FUZZ_SEED", 2019);
+    let cases = env_or("FUZZ_CASES", 2000);
+
+    let mut rng = Rng::new(seed);
+    let mut statuses = Vec::new();
+    for idx in 0..cases {
+        let case = generate(&mut rng);
+        if divergence(&case).is_some() {
+            // Panics are expected while minimizing, so keep them quiet
+            panic::set_hook(Box::new(|_| {}));
+            let case = minimize(case, |case| divergence(case).is_some());
+            let _ = panic::take_hook();
+            panic!(
+                "case {} of seed {} diverged, minimized to\n{}\n{}",
+                idx,
+                seed,
+                case,
+                divergence(&case).unwrap()
+            );
+        }
+        statuses.push(reference(&case).status);
+    }
+
+    // Make sure the generator reaches every kind of ending
+    let kinds = [
+        "Halted",
+        "NeedsInput",
+        "Limit",
+        "Fault(InvalidInstruction",
+        "Fault(NegativeAddress",
+        "Fault(WriteToImmediate",
+        "Fault(Overflow",
+    ];
+    for kind in kinds.iter() {
+        assert!(
+            statuses
+                .iter()
+                .any(|status| format!("{:?}", status).starts_with(kind)),
+            "no case ended with {}",
+            kind
+        );
+    }
+}
+
+#[test]
+fn minimize_reproducer() {
+    // Pretend that any program which outputs something and halts diverges
+    let interesting = |case: &Case| {
+        let outcome = reference(case);
+        outcome.status == Status::Halted && !outcome.output.is_empty()
+    };
+
+    let mut rng = Rng::new(7);
+    let case = (0..)
+        .map(|_| generate(&mut rng))
+        .find(|case| interesting(case) && case.program.len() > 10)
+        .unwrap();
+    let minimized = minimize(case.clone(), interesting);
+    assert!(interesting(&minimized));
+    assert!(minimized.program.len() <= 3, "{}", minimized);
+    assert!(minimized.input.is_empty());
+}